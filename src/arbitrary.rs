@@ -0,0 +1,29 @@
+use {
+    crate::{ext::floor_char_boundary, StrIndex, StrRange},
+    arbitrary::{Result, Unstructured},
+};
+
+impl StrRange {
+    /// An arbitrary range which is valid for indexing `s`.
+    ///
+    /// Both endpoints are on char boundaries and within `s`,
+    /// so fuzz targets can slice `s` with the result without panicking.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// # use arbitrary::Unstructured;
+    /// let s = "メカジキ";
+    /// let mut u = Unstructured::new(&[7, 2, 5]);
+    /// let range = StrRange::arbitrary_valid_for(&mut u, s).unwrap();
+    /// let _ = &s[range];
+    /// ```
+    pub fn arbitrary_valid_for(u: &mut Unstructured<'_>, s: &str) -> Result<StrRange> {
+        let start = floor_char_boundary(s, u.int_in_range(0..=s.len())?);
+        let end = floor_char_boundary(s, u.int_in_range(start..=s.len())?);
+        let start = StrIndex::from_str_len(&s[..start]);
+        let end = StrIndex::from_str_len(&s[..end]);
+        Ok(StrRange::from(start..end))
+    }
+}
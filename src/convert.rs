@@ -1,8 +1,10 @@
 use {
-    crate::{StrIndex, StrRange},
+    crate::{StrIndex, StrIndexNonZero, StrRange},
     core::{
         convert::{TryFrom, TryInto},
+        num::{NonZeroU32, ParseIntError, TryFromIntError},
         ops::{Range, RangeTo},
+        str::FromStr,
     },
 };
 
@@ -18,12 +20,44 @@ impl From<u32> for StrIndex {
     }
 }
 
+impl AsRef<u32> for StrIndex {
+    fn as_ref(&self) -> &u32 {
+        &self.raw
+    }
+}
+
 impl From<StrIndex> for usize {
     fn from(idx: StrIndex) -> Self {
         idx.raw as usize
     }
 }
 
+/// Losslessly convert to `f64`, e.g. for computing ratios.
+///
+/// ```rust
+/// # use str_index::*;
+/// assert_eq!(f64::from(StrIndex::from(50)), 50.0);
+/// assert_eq!(f64::from(StrIndex::from(25)) / f64::from(StrIndex::from(100)), 0.25);
+/// ```
+impl From<StrIndex> for f64 {
+    fn from(idx: StrIndex) -> Self {
+        f64::from(idx.raw)
+    }
+}
+
+impl TryFrom<StrIndex> for StrIndexNonZero {
+    type Error = TryFromIntError;
+    fn try_from(index: StrIndex) -> Result<Self, Self::Error> {
+        NonZeroU32::try_from(index.raw).map(|raw| StrIndexNonZero { raw })
+    }
+}
+
+impl From<StrIndexNonZero> for StrIndex {
+    fn from(index: StrIndexNonZero) -> Self {
+        StrIndex::from(index.raw.get())
+    }
+}
+
 impl TryFrom<usize> for StrIndex {
     type Error = <usize as TryInto<u32>>::Error;
     fn try_from(i: usize) -> Result<Self, Self::Error> {
@@ -48,3 +82,162 @@ impl From<RangeTo<StrIndex>> for StrRange {
         }
     }
 }
+
+/// Split a range into its `(start, end)` endpoints.
+///
+/// ```rust
+/// # use str_index::*;
+/// let range = StrRange::from(3.into()..8.into());
+/// let (start, end) = range.into();
+/// assert_eq!((start, end), (StrIndex::from(3), StrIndex::from(8)));
+/// ```
+impl From<StrRange> for (StrIndex, StrIndex) {
+    fn from(range: StrRange) -> Self {
+        (range.start, range.end)
+    }
+}
+
+impl FromStr for StrIndex {
+    type Err = ParseIntError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse::<u32>().map(StrIndex::from)
+    }
+}
+
+impl TryFrom<&str> for StrIndex {
+    type Error = ParseIntError;
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+/// An error which can be returned when parsing a [`StrRange`].
+///
+/// The accepted syntax is that of `StrRange`'s `Display`, `start..end`,
+/// or an inclusive range, `start..=last`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseStrRangeError {
+    pub(crate) kind: ParseStrRangeErrorKind,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseStrRangeError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ParseStrRangeErrorKind {
+    MissingDelimiter,
+    InvalidIndex(ParseIntError),
+    Inverted(StrRange),
+    TooLarge,
+}
+
+impl From<ParseStrRangeErrorKind> for ParseStrRangeError {
+    fn from(kind: ParseStrRangeErrorKind) -> Self {
+        ParseStrRangeError { kind }
+    }
+}
+
+impl FromStr for StrRange {
+    type Err = ParseStrRangeError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, end) = s
+            .split_once("..")
+            .ok_or(ParseStrRangeErrorKind::MissingDelimiter)?;
+        let start: StrIndex = start
+            .parse()
+            .map_err(ParseStrRangeErrorKind::InvalidIndex)?;
+        let end: StrIndex = match end.strip_prefix('=') {
+            Some(last) => last
+                .parse::<StrIndex>()
+                .map_err(ParseStrRangeErrorKind::InvalidIndex)?
+                .checked_add(1.into())
+                .ok_or(ParseStrRangeErrorKind::TooLarge)?,
+            None => end.parse().map_err(ParseStrRangeErrorKind::InvalidIndex)?,
+        };
+        StrRange::try_new(start, end).ok_or_else(|| {
+            // construct manually to bypass ordering assert!
+            ParseStrRangeErrorKind::Inverted(StrRange { start, end }).into()
+        })
+    }
+}
+
+impl TryFrom<&str> for StrRange {
+    type Error = ParseStrRangeError;
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+#[test]
+fn as_ref_u32() {
+    fn raw<T: AsRef<u32>>(t: T) -> u32 {
+        *t.as_ref()
+    }
+    assert_eq!(raw(StrIndex::from(42)), 42);
+}
+
+#[test]
+fn parse_str_index() {
+    assert_eq!(StrIndex::try_from("42"), Ok(StrIndex::from(42)));
+    assert_eq!(
+        StrIndex::try_from("4294967295"),
+        Ok(StrIndex::from(u32::MAX))
+    );
+    assert!(StrIndex::try_from("4294967296").is_err());
+    assert!(StrIndex::try_from("-1").is_err());
+    assert!(StrIndex::try_from("").is_err());
+    assert!(StrIndex::try_from("0x10").is_err());
+}
+
+#[test]
+fn parse_str_range() {
+    assert_eq!(
+        StrRange::try_from("0..10"),
+        Ok(StrRange::from(0.into()..10.into()))
+    );
+    assert_eq!(
+        StrRange::try_from("5..5"),
+        Ok(StrIndex::from(5).as_unit_range())
+    );
+    assert!(matches!(
+        StrRange::try_from("0..4294967296").map_err(|e| e.kind),
+        Err(ParseStrRangeErrorKind::InvalidIndex(_))
+    ));
+    assert!(matches!(
+        StrRange::try_from("0..1..2").map_err(|e| e.kind),
+        Err(ParseStrRangeErrorKind::InvalidIndex(_))
+    ));
+    assert_eq!(
+        StrRange::try_from("0..=9"),
+        Ok(StrRange::from(0.into()..10.into()))
+    );
+    assert_eq!(
+        StrRange::try_from("0..=4294967295").map_err(|e| e.kind),
+        Err(ParseStrRangeErrorKind::TooLarge)
+    );
+    assert_eq!(
+        StrRange::try_from("10").map_err(|e| e.kind),
+        Err(ParseStrRangeErrorKind::MissingDelimiter)
+    );
+    assert_eq!(
+        StrRange::try_from("10..0").map_err(|e| e.kind),
+        Err(ParseStrRangeErrorKind::Inverted(StrRange {
+            start: 10.into(),
+            end: 0.into(),
+        }))
+    );
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn parse_str_range_error_is_error() {
+    use std::string::ToString;
+    fn parse(s: &str) -> Result<StrRange, std::boxed::Box<dyn std::error::Error>> {
+        Ok(s.parse()?)
+    }
+    assert_eq!(parse("0..1").unwrap(), StrRange::from(0.into()..1.into()));
+    assert_eq!(
+        parse("0").unwrap_err().to_string(),
+        "missing `..` in string range",
+    );
+}
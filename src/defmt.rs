@@ -0,0 +1,16 @@
+use {
+    crate::{StrIndex, StrRange},
+    defmt::{Format, Formatter},
+};
+
+impl Format for StrIndex {
+    fn format(&self, f: Formatter) {
+        defmt::write!(f, "{=u32}", self.raw)
+    }
+}
+
+impl Format for StrRange {
+    fn format(&self, f: Formatter) {
+        defmt::write!(f, "{=u32}..{=u32}", self.start.raw, self.end.raw)
+    }
+}
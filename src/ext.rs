@@ -0,0 +1,474 @@
+use {
+    crate::{StrIndex, StrRange},
+    core::cmp,
+};
+
+/// Extension methods for accessing a `str` by [`StrIndex`].
+pub trait StrExt {
+    /// The byte at `index`, or `None` if `index` is out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// let s = "swordfish";
+    /// assert_eq!(s.byte_at(5.into()), Some(b'f'));
+    /// assert_eq!(s.byte_at(9.into()), None);
+    /// ```
+    fn byte_at(&self, index: StrIndex) -> Option<u8>;
+
+    /// The char starting at `index`,
+    /// or `None` if `index` is out of range or not on a char boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// let s = "メカジキ";
+    /// assert_eq!(s.char_at(3.into()), Some('カ'));
+    /// assert_eq!(s.char_at(4.into()), None);
+    /// assert_eq!(s.char_at(12.into()), None);
+    /// ```
+    fn char_at(&self, index: StrIndex) -> Option<char>;
+
+    /// The smallest range on char boundaries which covers `range`.
+    ///
+    /// The start is moved back and the end moved forward to the nearest char boundary,
+    /// so the result is always valid for slicing this string.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds of this string.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// let s = "メカジキ";
+    /// let range = StrRange::from(4.into()..7.into());
+    /// assert_eq!(s.snap_range(range), StrRange::from(3.into()..9.into()));
+    /// assert_eq!(&s[s.snap_range(range)], "カジ");
+    /// ```
+    fn snap_range(&self, range: StrRange) -> StrRange;
+
+    /// The bytes of `range` of this string, paired with their index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds of this string.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// let s = "swordfish";
+    /// let range = StrRange::from(5.into()..8.into());
+    /// assert_eq!(
+    ///     s.byte_ranges(range).collect::<Vec<_>>(),
+    ///     vec![(5.into(), b'f'), (6.into(), b'i'), (7.into(), b's')],
+    /// );
+    /// ```
+    fn byte_ranges(&self, range: StrRange) -> impl Iterator<Item = (StrIndex, u8)>;
+
+    /// Does `range` contain the whole char starting at `index`?
+    ///
+    /// Returns `false` if there is no char starting at `index`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// let s = "ab😂c";
+    /// let range = StrRange::from(0.into()..4.into());
+    /// assert!(range.contains_exclusive(2.into()));
+    /// assert!(!s.range_contains_char(range, 2.into()));
+    /// assert!(s.range_contains_char(range, 1.into()));
+    ///
+    /// let range = StrRange::from(2.into()..6.into());
+    /// assert!(s.range_contains_char(range, 2.into()));
+    /// assert!(!s.range_contains_char(range, 3.into()));
+    /// ```
+    fn range_contains_char(&self, range: StrRange, index: StrIndex) -> bool;
+
+    /// The first char of `range` of this string, and the rest of the range after it.
+    ///
+    /// Returns `None` if `range` is empty.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds or not on char boundaries of this string.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// let s = "メカジキ";
+    /// let range = StrRange::from(3.into()..12.into());
+    /// assert_eq!(
+    ///     s.split_first_char(range),
+    ///     Some(('カ', StrRange::from(6.into()..12.into()))),
+    /// );
+    /// assert_eq!(s.split_first_char(StrIndex::from(3).as_unit_range()), None);
+    /// ```
+    fn split_first_char(&self, range: StrRange) -> Option<(char, StrRange)>;
+
+    /// The last char of `range` of this string, and the rest of the range before it.
+    ///
+    /// Returns `None` if `range` is empty.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds or not on char boundaries of this string.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// let s = "メカジキ";
+    /// let range = StrRange::from(0.into()..9.into());
+    /// assert_eq!(
+    ///     s.split_last_char(range),
+    ///     Some(('ジ', StrRange::from(0.into()..6.into()))),
+    /// );
+    /// assert_eq!(s.split_last_char(StrIndex::from(3).as_unit_range()), None);
+    /// ```
+    fn split_last_char(&self, range: StrRange) -> Option<(char, StrRange)>;
+
+    /// The number of chars between `a` and `b` in this string, in either order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a` or `b` is out of bounds or not on a char boundary of this string.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// let s = "メカジキ";
+    /// assert_eq!(s.char_distance(3.into(), 12.into()), 3);
+    /// assert_eq!(s.char_distance(12.into(), 3.into()), 3);
+    /// assert_eq!(StrIndex::from(12) - StrIndex::from(3), StrIndex::from(9));
+    /// ```
+    fn char_distance(&self, a: StrIndex, b: StrIndex) -> usize;
+
+    /// The lines of `range` of this string, with their line number.
+    ///
+    /// Lines are numbered from one at the start of the string, not the start of `range`.
+    /// Line ranges are absolute, do not include the line terminator (`\n` or `\r\n`),
+    /// and are clipped to `range`; a `\r` ending `range` is excluded as well.
+    ///
+    /// As with `line_starts`, a `\n` ending `range` is followed by an empty final line,
+    /// and an empty `range` is a single empty line.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds or not on char boundaries of this string.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// let s = "one\ntwo\r\nthree\nfour";
+    /// let range = StrRange::from(5.into()..15.into());
+    /// assert_eq!(
+    ///     s.enumerate_lines(range).collect::<Vec<_>>(),
+    ///     vec![
+    ///         (2, StrRange::from(5.into()..7.into())),
+    ///         (3, StrRange::from(9.into()..14.into())),
+    ///         (4, StrRange::from(15.into()..15.into())),
+    ///     ],
+    /// );
+    ///
+    /// let range = StrRange::from(4.into()..8.into());
+    /// assert_eq!(
+    ///     s.enumerate_lines(range).collect::<Vec<_>>(),
+    ///     vec![(2, StrRange::from(4.into()..7.into()))],
+    /// );
+    /// ```
+    fn enumerate_lines(&self, range: StrRange) -> impl Iterator<Item = (u32, StrRange)>;
+
+    /// The number of bytes from `index` to the end of this string,
+    /// or zero if `index` is past the end.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// let s = "swordfish";
+    /// assert_eq!(s.remaining_from(5.into()), StrIndex::from(4));
+    /// assert_eq!(s.remaining_from(9.into()), StrIndex::from(0));
+    /// assert_eq!(s.remaining_from(20.into()), StrIndex::from(0));
+    /// ```
+    fn remaining_from(&self, index: StrIndex) -> StrIndex;
+
+    /// The first index in `range` for which `pred` is false, or `range.end()` if there is none.
+    ///
+    /// Like slice `partition_point`, `pred` must be true for a prefix of `range`
+    /// and false for the rest; the boundary is found by binary search.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds of this string.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// let s = "        return;";
+    /// let range = StrRange::from(0.into()..15.into());
+    /// let indent = s.partition_point(range, |i| s.byte_at(i) == Some(b' '));
+    /// assert_eq!(indent, StrIndex::from(8));
+    /// assert_eq!(s.partition_point(range, |_| true), range.end());
+    /// ```
+    fn partition_point(&self, range: StrRange, pred: impl Fn(StrIndex) -> bool) -> StrIndex;
+
+    /// Split `range` in two at the char boundary nearest `index`.
+    ///
+    /// `index` is first clamped into `range`, then snapped to the closer of the
+    /// surrounding char boundaries, preferring the earlier one on a tie.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds of this string.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// let s = "メカジキ";
+    /// let range = StrRange::from(0.into()..12.into());
+    /// assert_eq!(
+    ///     s.bisect_at(range, 4.into()),
+    ///     (StrRange::from(0.into()..3.into()), StrRange::from(3.into()..12.into())),
+    /// );
+    /// assert_eq!(
+    ///     s.bisect_at(range, 5.into()),
+    ///     (StrRange::from(0.into()..6.into()), StrRange::from(6.into()..12.into())),
+    /// );
+    /// assert_eq!(
+    ///     s.bisect_at(range, 20.into()),
+    ///     (range, StrRange::from(12.into()..12.into())),
+    /// );
+    /// ```
+    fn bisect_at(&self, range: StrRange, index: StrIndex) -> (StrRange, StrRange);
+
+    /// Clamp `range` to this string, then expand it outwards to the nearest char boundaries.
+    ///
+    /// Unlike [`snap_range`](StrExt::snap_range), this never panics,
+    /// and the result can always be used to index this string.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// let s = "メカジキ";
+    /// let range = s.clamp_range(StrRange::from(4.into()..20.into()));
+    /// assert_eq!(range, StrRange::from(3.into()..12.into()));
+    /// assert_eq!(&s[range], "カジキ");
+    ///
+    /// let range = s.clamp_range(StrRange::from(15.into()..20.into()));
+    /// assert_eq!(range, StrIndex::from(12).as_unit_range());
+    /// assert_eq!(&s[range], "");
+    /// ```
+    fn clamp_range(&self, range: StrRange) -> StrRange;
+}
+
+impl StrExt for str {
+    fn byte_at(&self, index: StrIndex) -> Option<u8> {
+        self.as_bytes().get(index.to_usize()).copied()
+    }
+
+    fn char_at(&self, index: StrIndex) -> Option<char> {
+        self.get(index.to_usize()..)?.chars().next()
+    }
+
+    fn snap_range(&self, range: StrRange) -> StrRange {
+        assert!(
+            range.end().to_usize() <= self.len(),
+            "string range {} out of bounds",
+            range,
+        );
+        let start = floor_char_boundary(self, range.start().to_usize());
+        let end = ceil_char_boundary(self, range.end().to_usize());
+        StrRange::from(StrIndex::from(start as u32)..StrIndex::from(end as u32))
+    }
+
+    fn byte_ranges(&self, range: StrRange) -> impl Iterator<Item = (StrIndex, u8)> {
+        let bytes = &self.as_bytes()[range.start().to_usize()..range.end().to_usize()];
+        range.indices().zip(bytes.iter().copied())
+    }
+
+    fn range_contains_char(&self, range: StrRange, index: StrIndex) -> bool {
+        match self.char_at(index) {
+            Some(c) => range.contains(index.range_for(StrIndex::from_char_len(c))),
+            None => false,
+        }
+    }
+
+    fn split_first_char(&self, range: StrRange) -> Option<(char, StrRange)> {
+        let c = self[range].chars().next()?;
+        Some((
+            c,
+            range.with_start(range.start() + StrIndex::from_char_len(c)),
+        ))
+    }
+
+    fn split_last_char(&self, range: StrRange) -> Option<(char, StrRange)> {
+        let c = self[range].chars().next_back()?;
+        Some((c, range.with_end(range.end() - StrIndex::from_char_len(c))))
+    }
+
+    fn char_distance(&self, a: StrIndex, b: StrIndex) -> usize {
+        let range = StrRange::from(cmp::min(a, b)..cmp::max(a, b));
+        self[range].chars().count()
+    }
+
+    fn enumerate_lines(&self, range: StrRange) -> impl Iterator<Item = (u32, StrRange)> {
+        let first = self[..range.start().to_usize()].matches('\n').count() as u32 + 1;
+        let mut start = range.start();
+        self[range]
+            .split('\n')
+            .zip(first..)
+            .map(move |(line, number)| {
+                let line_start = start;
+                let line_end = line_start + StrIndex::from_str_len(line);
+                // step over the `\n`; only the final line has none to step over
+                start = line_end.checked_add(1.into()).unwrap_or(line_end);
+                let content = line.strip_suffix('\r').unwrap_or(line);
+                (
+                    number,
+                    line_start.range_for(StrIndex::from_str_len(content)),
+                )
+            })
+    }
+
+    fn remaining_from(&self, index: StrIndex) -> StrIndex {
+        StrIndex::from_str_len(self).saturating_sub(index)
+    }
+
+    fn partition_point(&self, range: StrRange, pred: impl Fn(StrIndex) -> bool) -> StrIndex {
+        assert!(
+            range.end().to_usize() <= self.len(),
+            "string range {} out of bounds",
+            range,
+        );
+        let (mut low, mut high) = (range.start(), range.end());
+        while low < high {
+            let mid = low.range_to(high).midpoint();
+            if pred(mid) {
+                low = mid + StrIndex::from(1);
+            } else {
+                high = mid;
+            }
+        }
+        low
+    }
+
+    fn bisect_at(&self, range: StrRange, index: StrIndex) -> (StrRange, StrRange) {
+        assert!(
+            range.end().to_usize() <= self.len(),
+            "string range {} out of bounds",
+            range,
+        );
+        let index = cmp::min(cmp::max(index, range.start()), range.end()).to_usize();
+        let floor = floor_char_boundary(self, index);
+        let ceil = ceil_char_boundary(self, index);
+        let split = if index - floor <= ceil - index {
+            floor
+        } else {
+            ceil
+        };
+        let split = cmp::min(
+            cmp::max(StrIndex::from(split as u32), range.start()),
+            range.end(),
+        );
+        (
+            StrRange::from(range.start()..split),
+            StrRange::from(split..range.end()),
+        )
+    }
+
+    fn clamp_range(&self, range: StrRange) -> StrRange {
+        let len = StrIndex::from_str_len(self);
+        let start = cmp::min(range.start(), len);
+        let end = cmp::min(range.end(), len);
+        self.snap_range(StrRange::from(start..end))
+    }
+}
+
+/// Extension methods for accessing an `OsStr` by [`StrRange`].
+#[cfg(feature = "std")]
+pub trait OsStrIndexExt {
+    /// A subslice of this `OsStr`, or `None` if it is not valid UTF-8
+    /// or `range` is out of bounds or not on char boundaries.
+    ///
+    /// The encoding of an `OsStr` is platform specific,
+    /// so byte ranges are only meaningful when the `OsStr` is valid UTF-8.
+    /// This is always checked, so this returns `None` for any non-UTF-8 `OsStr`,
+    /// even if the requested range itself would be valid.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// # use std::ffi::OsStr;
+    /// let path = OsStr::new("src/lib.rs");
+    /// let range = StrRange::from(4.into()..7.into());
+    /// assert_eq!(path.get_str_range(range), Some(OsStr::new("lib")));
+    /// assert_eq!(path.get_str_range(StrRange::from(4.into()..20.into())), None);
+    /// ```
+    fn get_str_range(&self, range: StrRange) -> Option<&std::ffi::OsStr>;
+}
+
+#[cfg(feature = "std")]
+impl OsStrIndexExt for std::ffi::OsStr {
+    fn get_str_range(&self, range: StrRange) -> Option<&std::ffi::OsStr> {
+        let s = self.to_str()?;
+        let s = s.get(range.start().to_usize()..range.end().to_usize())?;
+        Some(std::ffi::OsStr::new(s))
+    }
+}
+
+/// The greatest char boundary in `s` no greater than `index`, which must be `<= s.len()`.
+pub(crate) fn floor_char_boundary(s: &str, mut index: usize) -> usize {
+    while !s.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+/// The least char boundary in `s` no less than `index`, which must be `<= s.len()`.
+pub(crate) fn ceil_char_boundary(s: &str, mut index: usize) -> usize {
+    while !s.is_char_boundary(index) {
+        index += 1;
+    }
+    index
+}
+
+#[test]
+fn clamp_range_always_slices() {
+    // ranges run past the end of each string, and multi-byte chars put endpoints mid-char
+    for s in &["", "メカジキ", "🐟a"] {
+        for start in 0..16 {
+            for end in start..16 {
+                let range = s.clamp_range(StrRange::from(start.into()..end.into()));
+                assert!(s
+                    .get(range.start().to_usize()..range.end().to_usize())
+                    .is_some());
+            }
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn enumerate_lines_matches_line_starts() {
+    for s in &["", "a", "a\n", "a\r\nb", "\n\n", "a\nb\r\n"] {
+        let full = StrRange::from(..StrIndex::from_str_len(s));
+        let starts: alloc::vec::Vec<_> = s
+            .enumerate_lines(full)
+            .map(|(_, line)| line.start())
+            .collect();
+        assert_eq!(starts, crate::line_starts(s), "{:?}", s);
+    }
+}
@@ -1,6 +1,9 @@
 use {
-    crate::{StrIndex, StrRange},
-    core::fmt,
+    crate::{
+        convert::{ParseStrRangeError, ParseStrRangeErrorKind},
+        StrIndex, StrIndexNonZero, StrRange,
+    },
+    core::{fmt, str},
 };
 
 impl fmt::Debug for StrIndex {
@@ -9,15 +12,46 @@ impl fmt::Debug for StrIndex {
     }
 }
 
+impl fmt::Debug for StrIndexNonZero {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&StrIndex::from(*self), f)
+    }
+}
+
+/// Formats like `u32`, honoring all formatter flags.
+///
+/// ```rust
+/// # use str_index::*;
+/// let index = StrIndex::from(42);
+/// assert_eq!(format!("{:05}", index), "00042");
+/// assert_eq!(format!("{:5}", index), "   42");
+/// assert_eq!(format!("{:<5}|", index), "42   |");
+/// assert_eq!(format!("{:*^6}", index), "**42**");
+/// assert_eq!(format!("{:+}", index), "+42");
+/// assert_eq!(format!("{:+05}", index), "+0042");
+/// ```
 impl fmt::Display for StrIndex {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         fmt::Display::fmt(&self.raw, f)
     }
 }
 
+/// Formats like `Range<StrIndex>`.
+/// The alternate flag additionally marks empty ranges.
+///
+/// ```rust
+/// # use str_index::*;
+/// let range = StrIndex::from(5).as_unit_range();
+/// assert_eq!(format!("{:?}", range), "5..5");
+/// assert_eq!(format!("{:#?}", range), "5..5 (empty)");
+/// ```
 impl fmt::Debug for StrRange {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        fmt::Debug::fmt(&(self.start()..self.end()), f)
+        fmt::Debug::fmt(&(self.start()..self.end()), f)?;
+        if f.alternate() && self.is_empty() {
+            f.write_str(" (empty)")?;
+        }
+        Ok(())
     }
 }
 
@@ -26,3 +60,74 @@ impl fmt::Display for StrRange {
         write!(f, "{}..{}", self.start(), self.end())
     }
 }
+
+pub(crate) struct DisplayGrouped {
+    pub(crate) index: StrIndex,
+    pub(crate) separator: char,
+}
+
+impl fmt::Display for DisplayGrouped {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // 10 digits and 3 separators of up to 4 bytes each
+        let mut buf = [0; 22];
+        let mut separator = [0; 4];
+        let separator = self.separator.encode_utf8(&mut separator).as_bytes();
+        let mut pos = buf.len();
+        let mut raw = self.index.raw;
+        let mut digits = 0;
+        loop {
+            if digits != 0 && digits % 3 == 0 {
+                pos -= separator.len();
+                buf[pos..pos + separator.len()].copy_from_slice(separator);
+            }
+            pos -= 1;
+            buf[pos] = b'0' + (raw % 10) as u8;
+            raw /= 10;
+            digits += 1;
+            if raw == 0 {
+                break;
+            }
+        }
+        f.pad(str::from_utf8(&buf[pos..]).expect("grouped digits are valid UTF-8"))
+    }
+}
+
+pub(crate) struct DebugCompact(pub(crate) StrRange);
+
+impl fmt::Debug for DebugCompact {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}..{}", self.0.start().raw, self.0.end().raw)
+    }
+}
+
+pub(crate) struct DisplayInclusive(pub(crate) StrRange);
+
+impl fmt::Display for DisplayInclusive {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}..={}",
+            self.0.start(),
+            self.0.end() - StrIndex::from(1)
+        )
+    }
+}
+
+pub(crate) struct DisplayWithLen(pub(crate) StrRange);
+
+impl fmt::Display for DisplayWithLen {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} [{}]", self.0, self.0.len())
+    }
+}
+
+impl fmt::Display for ParseStrRangeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.kind {
+            ParseStrRangeErrorKind::MissingDelimiter => f.write_str("missing `..` in string range"),
+            ParseStrRangeErrorKind::InvalidIndex(err) => write!(f, "invalid string index: {}", err),
+            ParseStrRangeErrorKind::Inverted(range) => write!(f, "invalid string range {}", range),
+            ParseStrRangeErrorKind::TooLarge => f.write_str("string range too large"),
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use {
+    crate::StrIndex,
+    core::{iter::FusedIterator, ops::Range},
+};
+
+/// An iterator over the byte indices of a [`StrRange`](crate::StrRange).
+///
+/// Created by [`StrRange::indices`](crate::StrRange::indices)
+/// and [`StrIndex::step_range`].
+#[derive(Debug, Clone)]
+pub struct StrRangeIndices {
+    pub(crate) raw: Range<u32>,
+}
+
+impl Iterator for StrRangeIndices {
+    type Item = StrIndex;
+
+    fn next(&mut self) -> Option<StrIndex> {
+        self.raw.next().map(StrIndex::from)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.raw.size_hint()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<StrIndex> {
+        self.raw.nth(n).map(StrIndex::from)
+    }
+}
+
+impl DoubleEndedIterator for StrRangeIndices {
+    fn next_back(&mut self) -> Option<StrIndex> {
+        self.raw.next_back().map(StrIndex::from)
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<StrIndex> {
+        self.raw.nth_back(n).map(StrIndex::from)
+    }
+}
+
+impl ExactSizeIterator for StrRangeIndices {}
+
+impl FusedIterator for StrRangeIndices {}
+
+#[test]
+fn len_matches_range_len() {
+    let range = crate::StrRange::from(3.into()..10.into());
+    let mut indices = range.indices();
+    assert_eq!(indices.len(), range.len().to_usize());
+    assert_eq!(indices.next(), Some(3.into()));
+    assert_eq!(indices.len(), 6);
+    assert_eq!(indices.next_back(), Some(9.into()));
+    assert_eq!(indices.len(), 5);
+    assert_eq!(indices.nth(1), Some(5.into()));
+    assert_eq!(indices.len(), 3);
+    assert_eq!(indices.nth_back(1), Some(7.into()));
+    assert_eq!(indices.len(), 1);
+    assert_eq!(indices.next_back(), Some(6.into()));
+    assert_eq!(indices.len(), 0);
+    assert_eq!(indices.next(), None);
+    assert_eq!(indices.size_hint(), (0, Some(0)));
+}
+
+#[test]
+fn empty() {
+    let mut indices = crate::StrIndex::from(4).as_unit_range().indices();
+    assert_eq!(indices.len(), 0);
+    assert_eq!(indices.next(), None);
+    assert_eq!(indices.next_back(), None);
+}
@@ -2,25 +2,78 @@
 
 #[cfg(feature = "alloc")]
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
-use core::{cmp, u32};
+use core::{
+    cmp,
+    convert::TryFrom,
+    hash::{Hash, Hasher},
+    mem,
+    num::NonZeroU32,
+    ops::{Bound, Range, RangeBounds, RangeFrom, RangeTo},
+};
 
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
 mod convert;
+#[cfg(feature = "defmt")]
+mod defmt;
+mod ext;
 mod fmt;
+mod iter;
 mod ops;
+#[cfg(feature = "proptest")]
+mod proptest;
 #[cfg(feature = "serde")]
 mod serde;
+#[cfg(feature = "serde")]
+pub mod serde_repr;
+
+#[cfg(feature = "std")]
+pub use crate::ext::OsStrIndexExt;
+#[cfg(feature = "serde")]
+pub use crate::serde::{BoundedStrIndex, PackedStrRange};
+pub use crate::{convert::ParseStrRangeError, ext::StrExt, iter::StrRangeIndices};
 
 /// An index into a string.
 ///
 /// The index is stored as a 32 bit integer,
 /// assuming we only deal with text shorter than 4 GiB.
-#[derive(Copy, Clone, Default, Ord, PartialOrd, Eq, PartialEq, Hash)]
+///
+/// Hashing a `StrIndex` is guaranteed to be exactly one `Hasher::write_u32` call,
+/// so identity hashers for integer keys (e.g. `nohash-hasher`) may be used.
+#[derive(Copy, Clone, Default, Ord, PartialOrd, Eq, PartialEq)]
 pub struct StrIndex {
     raw: u32,
 }
 
+impl Hash for StrIndex {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u32(self.raw)
+    }
+}
+
+const HIGH_BIT: u32 = 1 << 31;
+
 impl StrIndex {
+    /// Index from a raw `u32`, usable in `const` contexts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use str_index::*;
+    /// const KEYWORD_LENS: [StrIndex; 3] = [
+    ///     StrIndex::from_u32(2), // fn
+    ///     StrIndex::from_u32(3), // let
+    ///     StrIndex::from_u32(5), // match
+    /// ];
+    /// assert_eq!(KEYWORD_LENS[1], StrIndex::from_str_len("let"));
+    /// ```
+    pub const fn from_u32(raw: u32) -> Self {
+        StrIndex { raw }
+    }
+
     /// Index equal to the string length of this `char`.
     ///
     /// # Examples
@@ -54,11 +107,71 @@ impl StrIndex {
         }
     }
 
+    /// Index equal to the length of this UTF-16 string.
+    ///
+    /// Note that this index counts UTF-16 code units, not UTF-8 bytes,
+    /// so it is not a valid index into the equivalent `str`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use str_index::*;
+    /// let units: Vec<u16> = "a😂".encode_utf16().collect();
+    /// assert_eq!(
+    ///     StrIndex::from_utf16_len(&units),
+    ///     StrIndex::from(3),
+    /// );
+    /// ```
+    pub fn from_utf16_len(units: &[u16]) -> Self {
+        assert!(units.len() < u32::MAX as usize, "string index too large");
+        StrIndex {
+            raw: units.len() as u32,
+        }
+    }
+
+    /// Index equal to the length of this string,
+    /// **clamped to `u32::MAX` if the string is 4 GiB or longer**.
+    ///
+    /// Indices derived from a clamped length do not correctly index the string;
+    /// only use this where best-effort offsets are acceptable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use str_index::*;
+    /// assert_eq!(
+    ///     StrIndex::saturating_from_str_len("メカジキ"),
+    ///     StrIndex::from(12),
+    /// );
+    /// ```
+    pub fn saturating_from_str_len(s: &str) -> Self {
+        StrIndex::saturating_from_len(s.len())
+    }
+
+    fn saturating_from_len(len: usize) -> Self {
+        StrIndex::from(u32::try_from(len).unwrap_or(u32::MAX))
+    }
+
     /// This index as a raw `usize`.
     pub fn to_usize(self) -> usize {
         self.into()
     }
 
+    /// This index as the nearest `f32`.
+    ///
+    /// Unlike the lossless `f64::from`, indices above 2<sup>24</sup> may be rounded.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// assert_eq!(StrIndex::from(50).to_f32(), 50.0);
+    /// assert_eq!(StrIndex::from(16_777_217).to_f32(), 16_777_216.0);
+    /// ```
+    pub fn to_f32(self) -> f32 {
+        self.raw as f32
+    }
+
     /// Checked integer addition.
     pub fn checked_add(self, rhs: Self) -> Option<Self> {
         self.raw.checked_add(rhs.raw).map(StrIndex::from)
@@ -69,6 +182,175 @@ impl StrIndex {
         self.raw.checked_sub(rhs.raw).map(StrIndex::from)
     }
 
+    /// Saturating integer subtraction.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// assert_eq!(StrIndex::from(10).saturating_sub(4.into()), StrIndex::from(6));
+    /// assert_eq!(StrIndex::from(0).saturating_sub(10.into()), StrIndex::from(0));
+    /// ```
+    pub fn saturating_sub(self, rhs: Self) -> Self {
+        StrIndex::from(self.raw.saturating_sub(rhs.raw))
+    }
+
+    /// Overflowing integer subtraction.
+    ///
+    /// Returns the wrapped difference along with whether the subtraction underflowed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// let (diff, underflowed) = StrIndex::from(3).overflowing_sub(5.into());
+    /// assert_eq!(diff, StrIndex::from(u32::MAX - 1));
+    /// assert!(underflowed);
+    /// assert_eq!(
+    ///     StrIndex::from(5).overflowing_sub(3.into()),
+    ///     (StrIndex::from(2), false),
+    /// );
+    /// ```
+    pub fn overflowing_sub(self, rhs: Self) -> (Self, bool) {
+        let (raw, overflowed) = self.raw.overflowing_sub(rhs.raw);
+        (StrIndex::from(raw), overflowed)
+    }
+
+    /// Integer division, rounding up.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use str_index::*;
+    /// assert_eq!(
+    ///     StrIndex::from(10).div_ceil(StrIndex::from(3)),
+    ///     StrIndex::from(4),
+    /// );
+    /// ```
+    pub fn div_ceil(self, rhs: Self) -> Self {
+        StrIndex::from(self.raw.div_ceil(rhs.raw))
+    }
+
+    /// Euclidean division.
+    ///
+    /// As indices are unsigned, this is the same as regular division.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use str_index::*;
+    /// assert_eq!(
+    ///     StrIndex::from(10).div_euclid(StrIndex::from(4)),
+    ///     StrIndex::from(2),
+    /// );
+    /// ```
+    pub fn div_euclid(self, rhs: Self) -> Self {
+        StrIndex::from(self.raw.div_euclid(rhs.raw))
+    }
+
+    /// Euclidean remainder, e.g. for tab stop math.
+    ///
+    /// As indices are unsigned, this is the same as regular remainder.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use str_index::*;
+    /// let tab_width = StrIndex::from(4);
+    /// assert_eq!(
+    ///     StrIndex::from(10).rem_euclid(tab_width),
+    ///     StrIndex::from(2),
+    /// );
+    /// ```
+    pub fn rem_euclid(self, rhs: Self) -> Self {
+        StrIndex::from(self.raw.rem_euclid(rhs.raw))
+    }
+
+    /// Whether this index is a multiple of `rhs`.
+    ///
+    /// Like `u32::is_multiple_of`, this does not panic when `rhs` is zero;
+    /// zero is the only multiple of zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use str_index::*;
+    /// let tab_width = StrIndex::from(4);
+    /// assert!(StrIndex::from(8).is_multiple_of(tab_width));
+    /// assert!(!StrIndex::from(10).is_multiple_of(tab_width));
+    /// assert!(StrIndex::from(0).is_multiple_of(0.into()));
+    /// assert!(!StrIndex::from(8).is_multiple_of(0.into()));
+    /// ```
+    pub fn is_multiple_of(self, rhs: Self) -> bool {
+        self.raw.is_multiple_of(rhs.raw)
+    }
+
+    /// Round down to a multiple of `align`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `align` is not a power of two.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use str_index::*;
+    /// assert_eq!(
+    ///     StrIndex::from(13).align_down(StrIndex::from(8)),
+    ///     StrIndex::from(8),
+    /// );
+    /// ```
+    pub fn align_down(self, align: StrIndex) -> Self {
+        assert!(
+            align.raw.is_power_of_two(),
+            "alignment must be a power of two"
+        );
+        StrIndex::from(self.raw & !(align.raw - 1))
+    }
+
+    /// Round up to a multiple of `align`, saturating at `u32::MAX`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `align` is not a power of two.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use str_index::*;
+    /// assert_eq!(
+    ///     StrIndex::from(13).align_up(StrIndex::from(8)),
+    ///     StrIndex::from(16),
+    /// );
+    /// assert_eq!(
+    ///     StrIndex::from(u32::MAX - 1).align_up(StrIndex::from(8)),
+    ///     StrIndex::from(u32::MAX),
+    /// );
+    /// ```
+    pub fn align_up(self, align: StrIndex) -> Self {
+        assert!(
+            align.raw.is_power_of_two(),
+            "alignment must be a power of two"
+        );
+        let mask = align.raw - 1;
+        match self.raw.checked_add(mask) {
+            Some(raw) => StrIndex::from(raw & !mask),
+            None => StrIndex::from(u32::MAX),
+        }
+    }
+
     /// A range starting at this index.
     ///
     /// # Example
@@ -85,6 +367,23 @@ impl StrIndex {
         StrRange::from(self..self + len)
     }
 
+    /// A range starting at this index, or `None` if its end would overflow.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// let point = StrIndex::from(u32::MAX - 5);
+    /// assert_eq!(
+    ///     point.checked_range_for(5.into()),
+    ///     Some(StrRange::from(point..u32::MAX.into())),
+    /// );
+    /// assert_eq!(point.checked_range_for(6.into()), None);
+    /// ```
+    pub fn checked_range_for(self, len: StrIndex) -> Option<StrRange> {
+        self.checked_add(len).map(|end| StrRange::from(self..end))
+    }
+
     /// A range from this index to another.
     ///
     /// # Panics
@@ -121,6 +420,136 @@ impl StrIndex {
     pub fn as_unit_range(self) -> StrRange {
         StrRange::from(self..self)
     }
+
+    /// Every index from this one up to (but excluding) `end`.
+    ///
+    /// This is empty if `end <= self`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// let start = StrIndex::from(2);
+    /// let end = StrIndex::from(5);
+    /// assert_eq!(
+    ///     start.step_range(end).map(u32::from).collect::<Vec<_>>(),
+    ///     vec![2, 3, 4],
+    /// );
+    /// assert_eq!(
+    ///     start.step_range(end).rev().map(u32::from).collect::<Vec<_>>(),
+    ///     vec![4, 3, 2],
+    /// );
+    /// assert_eq!(start.step_range(end).len(), 3);
+    /// assert_eq!(end.step_range(start).next(), None);
+    /// assert_eq!(start.step_range(start).len(), 0);
+    /// ```
+    pub fn step_range(self, end: StrIndex) -> StrRangeIndices {
+        StrRangeIndices {
+            raw: self.raw..end.raw,
+        }
+    }
+
+    /// Is this index a position within `s`? (Including the end of `s`.)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// let s = "swordfish";
+    /// assert!(StrIndex::from(9).within(s));
+    /// assert!(!StrIndex::from(10).within(s));
+    /// ```
+    pub fn within(self, s: &str) -> bool {
+        self.to_usize() <= s.len()
+    }
+
+    /// Is this index the position of a byte within `s`? (Excluding the end of `s`.)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// let s = "swordfish";
+    /// assert!(StrIndex::from(8).within_strict(s));
+    /// assert!(!StrIndex::from(9).within_strict(s));
+    /// ```
+    pub fn within_strict(self, s: &str) -> bool {
+        self.to_usize() < s.len()
+    }
+
+    /// Display this index with digits grouped in threes by `separator`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// let index = StrIndex::from(1048576);
+    /// assert_eq!(index.display_grouped(',').to_string(), "1,048,576");
+    /// assert_eq!(index.display_grouped('_').to_string(), "1_048_576");
+    /// assert_eq!(StrIndex::from(999).display_grouped(',').to_string(), "999");
+    /// ```
+    pub fn display_grouped(self, separator: char) -> impl core::fmt::Display {
+        fmt::DisplayGrouped {
+            index: self,
+            separator,
+        }
+    }
+
+    /// This index with the high bit set or cleared.
+    ///
+    /// The high bit methods allow using the top bit of an index as a flag.
+    /// This assumes the index itself fits in 31 bits, i.e. the string is shorter than 2 GiB.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// let index = StrIndex::from(42);
+    /// let tagged = index.with_high_bit(true);
+    /// assert!(tagged.high_bit());
+    /// assert_eq!(tagged.without_high_bit(), index);
+    /// assert!(!tagged.with_high_bit(false).high_bit());
+    /// assert_eq!(tagged.with_high_bit(false), index);
+    /// ```
+    pub fn with_high_bit(self, set: bool) -> Self {
+        if set {
+            StrIndex::from(self.raw | HIGH_BIT)
+        } else {
+            self.without_high_bit()
+        }
+    }
+
+    /// Is the high bit of this index set?
+    ///
+    /// See [`with_high_bit`](StrIndex::with_high_bit).
+    pub fn high_bit(self) -> bool {
+        self.raw & HIGH_BIT != 0
+    }
+
+    /// This index with the high bit cleared.
+    ///
+    /// See [`with_high_bit`](StrIndex::with_high_bit).
+    pub fn without_high_bit(self) -> Self {
+        StrIndex::from(self.raw & !HIGH_BIT)
+    }
+}
+
+/// A nonzero [`StrIndex`].
+///
+/// Like `NonZeroU32`, this allows `Option<StrIndexNonZero>` to be the same size as `StrIndex`,
+/// for when `0` can be reserved as a "none" sentinel.
+///
+/// # Examples
+///
+/// ```rust
+/// # use {core::convert::TryFrom, str_index::*};
+/// let index = StrIndexNonZero::try_from(StrIndex::from(5)).unwrap();
+/// assert_eq!(StrIndex::from(index), StrIndex::from(5));
+/// assert!(StrIndexNonZero::try_from(StrIndex::from(0)).is_err());
+/// ```
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct StrIndexNonZero {
+    raw: NonZeroU32,
 }
 
 /// A range of a string, represented as a half-open range of `StrIndex`.
@@ -151,13 +580,97 @@ impl StrIndex {
 /// # let end = StrIndex::from(20);
 /// let this_panics = StrRange::from(end..start);
 /// ```
+///
+/// Ranges are ordered by start index, then by end index.
+/// As ranges with the same start are ordered by end exactly when they are ordered by length,
+/// this is also the by start, then by length span ordering; no separate sort key is needed.
+///
+/// ```rust
+/// # use str_index::{StrRange, StrIndex};
+/// let mut ranges = vec![
+///     StrRange::from(StrIndex::from(5)..StrIndex::from(10)),
+///     StrRange::from(StrIndex::from(0)..StrIndex::from(20)),
+///     StrRange::from(StrIndex::from(5)..StrIndex::from(7)),
+///     StrRange::from(StrIndex::from(0)..StrIndex::from(3)),
+/// ];
+/// ranges.sort();
+/// assert_eq!(
+///     ranges,
+///     vec![
+///         StrRange::from(StrIndex::from(0)..StrIndex::from(3)),
+///         StrRange::from(StrIndex::from(0)..StrIndex::from(20)),
+///         StrRange::from(StrIndex::from(5)..StrIndex::from(7)),
+///         StrRange::from(StrIndex::from(5)..StrIndex::from(10)),
+///     ],
+/// );
+/// ```
 #[derive(Copy, Clone, Default, Ord, PartialOrd, Eq, PartialEq, Hash)]
 pub struct StrRange {
     start: StrIndex,
     end: StrIndex,
 }
 
+// Guard the layout of the index types against accidental growth.
+const _: () = assert!(mem::size_of::<StrIndex>() == 4);
+const _: () = assert!(mem::size_of::<StrRange>() == 8);
+
 impl StrRange {
+    /// Resolve generic range bounds into a range of a string of length `len`,
+    /// as slice indexing does.
+    ///
+    /// An unbounded start resolves to `0` and an unbounded end to `len`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resolved range is decreasing or ends after `len`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// # use std::ops::Bound;
+    /// let len = StrIndex::from(10);
+    /// let (a, b) = (StrIndex::from(2), StrIndex::from(5));
+    /// assert_eq!(StrRange::from_bounds(.., len), StrRange::from(0.into()..10.into()));
+    /// assert_eq!(StrRange::from_bounds(a.., len), StrRange::from(2.into()..10.into()));
+    /// assert_eq!(StrRange::from_bounds(..b, len), StrRange::from(0.into()..5.into()));
+    /// assert_eq!(StrRange::from_bounds(..=b, len), StrRange::from(0.into()..6.into()));
+    /// assert_eq!(StrRange::from_bounds(a..b, len), StrRange::from(2.into()..5.into()));
+    /// assert_eq!(StrRange::from_bounds(a..=b, len), StrRange::from(2.into()..6.into()));
+    /// assert_eq!(
+    ///     StrRange::from_bounds((Bound::Excluded(a), Bound::Included(b)), len),
+    ///     StrRange::from(3.into()..6.into()),
+    /// );
+    /// ```
+    ///
+    /// ```rust,should_panic
+    /// # use str_index::*;
+    /// let this_panics = StrRange::from_bounds(..StrIndex::from(20), 10.into());
+    /// ```
+    pub fn from_bounds<R: RangeBounds<StrIndex>>(bounds: R, len: StrIndex) -> StrRange {
+        let start = match bounds.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start
+                .checked_add(1.into())
+                .expect("attempted to index string from after maximum index"),
+            Bound::Unbounded => 0.into(),
+        };
+        let end = match bounds.end_bound() {
+            Bound::Included(&end) => end
+                .checked_add(1.into())
+                .expect("attempted to index string up to maximum index"),
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => len,
+        };
+        assert!(
+            end <= len,
+            "string range end index {} out of range for string of length {}",
+            end,
+            len,
+        );
+        StrRange::from(start..end)
+    }
+
     /// The (inclusive) start index of this range.
     pub fn start(self) -> StrIndex {
         self.start
@@ -169,8 +682,13 @@ impl StrRange {
     }
 
     /// The length of this range.
+    ///
+    /// A well-formed range always has `start <= end`, so this is just `end - start`.
+    /// Should a malformed range escape checking, its length is reported as zero
+    /// (and debug builds assert).
     pub fn len(self) -> StrIndex {
-        self.end() - self.start()
+        debug_assert!(self.start() <= self.end(), "invalid string range {}", self);
+        self.end().saturating_sub(self.start())
     }
 
     /// Is this range a unit range?
@@ -179,9 +697,24 @@ impl StrRange {
         self.start() == self.end()
     }
 
-    /// A range with an adjusted end.
+    /// Do these ranges have the same length, regardless of position?
     ///
-    /// # Panics
+    /// # Example
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// let left = StrRange::from(0.into()..5.into());
+    /// let right = StrRange::from(10.into()..15.into());
+    /// assert_ne!(left, right);
+    /// assert!(left.same_len(right));
+    /// ```
+    pub fn same_len(self, other: StrRange) -> bool {
+        self.len() == other.len()
+    }
+
+    /// A range with an adjusted end.
+    ///
+    /// # Panics
     ///
     /// Panics if `self.end() < start`.
     ///
@@ -221,6 +754,281 @@ impl StrRange {
         StrRange::from(self.start()..end)
     }
 
+    /// A range with an adjusted start, or `None` if `self.end() < start`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// let range = StrRange::from(5.into()..10.into());
+    /// assert_eq!(
+    ///     range.checked_with_start(0.into()),
+    ///     Some(StrRange::from(0.into()..10.into())),
+    /// );
+    /// assert_eq!(range.checked_with_start(15.into()), None);
+    /// ```
+    pub fn checked_with_start(self, start: StrIndex) -> Option<StrRange> {
+        if start <= self.end() {
+            Some(StrRange::from(start..self.end()))
+        } else {
+            None
+        }
+    }
+
+    /// A range with an adjusted end, or `None` if `end < self.start()`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// let range = StrRange::from(5.into()..10.into());
+    /// assert_eq!(
+    ///     range.checked_with_end(15.into()),
+    ///     Some(StrRange::from(5.into()..15.into())),
+    /// );
+    /// assert_eq!(range.checked_with_end(0.into()), None);
+    /// ```
+    pub fn checked_with_end(self, end: StrIndex) -> Option<StrRange> {
+        if self.start() <= end {
+            Some(StrRange::from(self.start()..end))
+        } else {
+            None
+        }
+    }
+
+    /// A range from `start` to `end`, or `None` if `end < start`.
+    pub(crate) fn try_new(start: StrIndex, end: StrIndex) -> Option<StrRange> {
+        start.as_unit_range().checked_with_end(end)
+    }
+
+    /// A range with the same start and an adjusted length.
+    ///
+    /// The end saturates at `u32::MAX`; use [`checked_with_len`] to detect overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// let range = StrRange::from(5.into()..10.into());
+    /// assert_eq!(range.with_len(8.into()), StrRange::from(5.into()..13.into()));
+    /// assert_eq!(range.with_len(2.into()), StrRange::from(5.into()..7.into()));
+    /// assert_eq!(
+    ///     range.with_len(u32::MAX.into()),
+    ///     StrRange::from(5.into()..u32::MAX.into()),
+    /// );
+    /// ```
+    ///
+    /// [`checked_with_len`]: StrRange::checked_with_len
+    pub fn with_len(self, len: StrIndex) -> StrRange {
+        let end = self
+            .start()
+            .checked_add(len)
+            .unwrap_or(StrIndex::from(u32::MAX));
+        StrRange::from(self.start()..end)
+    }
+
+    /// A range with the same start and an adjusted length, or `None` if the end would overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// let range = StrRange::from(5.into()..10.into());
+    /// assert_eq!(
+    ///     range.checked_with_len(2.into()),
+    ///     Some(StrRange::from(5.into()..7.into())),
+    /// );
+    /// assert_eq!(range.checked_with_len(u32::MAX.into()), None);
+    /// ```
+    pub fn checked_with_len(self, len: StrIndex) -> Option<StrRange> {
+        self.start().checked_range_for(len)
+    }
+
+    /// The index halfway through this range, rounding down.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// let range = StrRange::from(10.into()..15.into());
+    /// assert_eq!(range.midpoint(), StrIndex::from(12));
+    /// ```
+    pub fn midpoint(self) -> StrIndex {
+        StrIndex::from(self.start().raw + self.len().raw / 2)
+    }
+
+    /// This range split at its midpoint.
+    ///
+    /// The halves are contiguous, cover this range exactly, and differ in length by at most one.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// let range = StrRange::from(10.into()..15.into());
+    /// let (left, right) = range.halves();
+    /// assert_eq!(left, StrRange::from(10.into()..12.into()));
+    /// assert_eq!(right, StrRange::from(12.into()..15.into()));
+    /// assert_eq!(left.merge(right), range);
+    /// assert!(right.len() - left.len() <= StrIndex::from(1));
+    /// ```
+    pub fn halves(self) -> (StrRange, StrRange) {
+        let midpoint = self.midpoint();
+        (self.with_end(midpoint), self.with_start(midpoint))
+    }
+
+    /// This range with its endpoints in order.
+    ///
+    /// A well-formed range is always ordered, so is returned unchanged;
+    /// this is a safety net for ranges built from unchecked sources.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// let range = StrRange::from(5.into()..10.into());
+    /// assert_eq!(range.normalize(), range);
+    /// ```
+    pub fn normalize(self) -> StrRange {
+        if self.start <= self.end {
+            self
+        } else {
+            StrRange {
+                start: self.end,
+                end: self.start,
+            }
+        }
+    }
+
+    /// This range extended by `amount` on both sides,
+    /// or `None` if the start would underflow or the end would overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// let range = StrRange::from(5.into()..10.into());
+    /// assert_eq!(
+    ///     range.checked_grow(5.into()),
+    ///     Some(StrRange::from(0.into()..15.into())),
+    /// );
+    /// assert_eq!(range.checked_grow(6.into()), None);
+    /// ```
+    pub fn checked_grow(self, amount: StrIndex) -> Option<StrRange> {
+        let start = self.start().checked_sub(amount)?;
+        let end = self.end().checked_add(amount)?;
+        Some(StrRange::from(start..end))
+    }
+
+    /// This range reduced by `amount` on both sides,
+    /// or `None` if that would leave it decreasing.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// let range = StrRange::from(5.into()..10.into());
+    /// assert_eq!(
+    ///     range.checked_shrink(2.into()),
+    ///     Some(StrRange::from(7.into()..8.into())),
+    /// );
+    /// assert_eq!(range.checked_shrink(3.into()), None);
+    /// ```
+    pub fn checked_shrink(self, amount: StrIndex) -> Option<StrRange> {
+        let start = self.start().checked_add(amount)?;
+        let end = self.end().checked_sub(amount)?;
+        if start <= end {
+            Some(StrRange::from(start..end))
+        } else {
+            None
+        }
+    }
+
+    /// This range with `f` applied to both endpoints.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the mapped range is decreasing; use [`try_map_indices`] if `f` isn't monotonic.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// let range = StrRange::from(5.into()..10.into());
+    /// assert_eq!(
+    ///     range.map_indices(|index| index + StrIndex::from(3)),
+    ///     StrRange::from(8.into()..13.into()),
+    /// );
+    /// ```
+    ///
+    /// ```rust,should_panic
+    /// # use str_index::*;
+    /// let range = StrRange::from(5.into()..10.into());
+    /// let this_panics = range.map_indices(|index| StrIndex::from(20) - index);
+    /// ```
+    ///
+    /// [`try_map_indices`]: StrRange::try_map_indices
+    pub fn map_indices<F: Fn(StrIndex) -> StrIndex>(self, f: F) -> StrRange {
+        StrRange::from(f(self.start())..f(self.end()))
+    }
+
+    /// This range with `f` applied to both endpoints, or `None` if the result is decreasing.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// let range = StrRange::from(5.into()..10.into());
+    /// assert_eq!(
+    ///     range.try_map_indices(|index| index + StrIndex::from(3)),
+    ///     Some(StrRange::from(8.into()..13.into())),
+    /// );
+    /// assert_eq!(
+    ///     range.try_map_indices(|index| StrIndex::from(20) - index),
+    ///     None,
+    /// );
+    /// ```
+    pub fn try_map_indices<F: Fn(StrIndex) -> StrIndex>(self, f: F) -> Option<StrRange> {
+        let start = f(self.start());
+        let end = f(self.end());
+        if start <= end {
+            Some(StrRange::from(start..end))
+        } else {
+            None
+        }
+    }
+
+    /// This range moved from being relative to `old_origin` to being relative to `new_origin`,
+    /// or `None` if either endpoint under- or overflows.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// let range = StrRange::from(15.into()..20.into());
+    /// assert_eq!(
+    ///     range.rebase(10.into(), 0.into()),
+    ///     Some(StrRange::from(5.into()..10.into())),
+    /// );
+    /// assert_eq!(
+    ///     range.rebase(10.into(), 100.into()),
+    ///     Some(StrRange::from(105.into()..110.into())),
+    /// );
+    /// assert_eq!(range.rebase(16.into(), 0.into()), None);
+    /// assert_eq!(range.rebase(0.into(), u32::MAX.into()), None);
+    /// ```
+    pub fn rebase(self, old_origin: StrIndex, new_origin: StrIndex) -> Option<StrRange> {
+        let start = self
+            .start()
+            .checked_sub(old_origin)?
+            .checked_add(new_origin)?;
+        let end = self
+            .end()
+            .checked_sub(old_origin)?
+            .checked_add(new_origin)?;
+        Some(StrRange::from(start..end))
+    }
+
     /// Are these ranges disjoint?
     ///
     /// Ranges that touch end to start are disjoint, as no byte is in both ranges.
@@ -330,6 +1138,39 @@ impl StrRange {
         StrRange::from(start..end)
     }
 
+    /// The range that covers both `self` and `other`, if they are exactly adjacent.
+    ///
+    /// Unlike [`merge`](StrRange::merge), ranges that overlap or have a gap between them
+    /// return `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// let left = StrRange::from(0.into()..10.into());
+    /// let right = StrRange::from(10.into()..20.into());
+    /// assert_eq!(
+    ///     left.merge_adjacent(right),
+    ///     Some(StrRange::from(0.into()..20.into())),
+    /// );
+    /// assert_eq!(right.merge_adjacent(left), left.merge_adjacent(right));
+    ///
+    /// let overlapping = StrRange::from(5.into()..15.into());
+    /// assert_eq!(left.merge_adjacent(overlapping), None);
+    ///
+    /// let gapped = StrRange::from(15.into()..20.into());
+    /// assert_eq!(left.merge_adjacent(gapped), None);
+    /// ```
+    pub fn merge_adjacent(self, other: Self) -> Option<Self> {
+        if self.end() == other.start() {
+            Some(StrRange::from(self.start()..other.end()))
+        } else if other.end() == self.start() {
+            Some(StrRange::from(other.start()..self.end()))
+        } else {
+            None
+        }
+    }
+
     /// Does this range contain `other`?
     ///
     /// `other` must be completely within `self`, but may share endpoints.
@@ -362,4 +1203,811 @@ impl StrRange {
     pub fn contains_exclusive(self, index: StrIndex) -> bool {
         self.start() <= index && index < self.end()
     }
+
+    /// The parts of `within` before and after this range.
+    ///
+    /// Empty gaps are `None`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `within` does not contain this range.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// let within = StrRange::from(0.into()..30.into());
+    /// assert_eq!(
+    ///     StrRange::from(0.into()..10.into()).complement(within),
+    ///     (None, Some(StrRange::from(10.into()..30.into()))),
+    /// );
+    /// assert_eq!(
+    ///     StrRange::from(10.into()..20.into()).complement(within),
+    ///     (
+    ///         Some(StrRange::from(0.into()..10.into())),
+    ///         Some(StrRange::from(20.into()..30.into())),
+    ///     ),
+    /// );
+    /// assert_eq!(
+    ///     StrRange::from(20.into()..30.into()).complement(within),
+    ///     (Some(StrRange::from(0.into()..20.into())), None),
+    /// );
+    /// ```
+    pub fn complement(self, within: StrRange) -> (Option<StrRange>, Option<StrRange>) {
+        assert!(
+            within.contains(self),
+            "string range {} not within {}",
+            self,
+            within,
+        );
+        let left = within.with_end(self.start());
+        let right = within.with_start(self.end());
+        (
+            Some(left).filter(|&it| !it.is_empty()),
+            Some(right).filter(|&it| !it.is_empty()),
+        )
+    }
+
+    /// The fragments of this range not covered by any of `others`.
+    ///
+    /// `others` must be sorted by start index and disjoint (as e.g. the output of [`coalesce`]).
+    /// Empty fragments are not yielded.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// let range = StrRange::from(0.into()..30.into());
+    /// let holes = vec![
+    ///     StrRange::from(5.into()..10.into()),
+    ///     StrRange::from(15.into()..20.into()),
+    /// ];
+    /// assert_eq!(
+    ///     range.difference(holes).collect::<Vec<_>>(),
+    ///     vec![
+    ///         StrRange::from(0.into()..5.into()),
+    ///         StrRange::from(10.into()..15.into()),
+    ///         StrRange::from(20.into()..30.into()),
+    ///     ],
+    /// );
+    ///
+    /// let holes = vec![StrRange::from(0.into()..10.into())];
+    /// assert_eq!(
+    ///     range.difference(holes).collect::<Vec<_>>(),
+    ///     vec![StrRange::from(10.into()..30.into())],
+    /// );
+    /// ```
+    pub fn difference<I: IntoIterator<Item = StrRange>>(
+        self,
+        others: I,
+    ) -> impl Iterator<Item = StrRange> {
+        let mut others = others.into_iter();
+        let mut cursor = Some(self.start());
+        core::iter::from_fn(move || loop {
+            let start = cursor.filter(|&start| start < self.end())?;
+            match others.next() {
+                Some(hole) => {
+                    let end = cmp::min(hole.start(), self.end());
+                    cursor = Some(cmp::max(start, hole.end()));
+                    if start < end {
+                        return Some(StrRange::from(start..end));
+                    }
+                }
+                None => {
+                    cursor = None;
+                    return Some(StrRange::from(start..self.end()));
+                }
+            }
+        })
+    }
+
+    /// The subranges of this range between each of `points`.
+    ///
+    /// # Panics
+    ///
+    /// Panics (when reached) if a point is outside this range or less than the previous point.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// let range = StrRange::from(0.into()..10.into());
+    /// let points = vec![StrIndex::from(3), StrIndex::from(7)];
+    /// assert_eq!(
+    ///     range.split_at_each(points).collect::<Vec<_>>(),
+    ///     vec![
+    ///         StrRange::from(0.into()..3.into()),
+    ///         StrRange::from(3.into()..7.into()),
+    ///         StrRange::from(7.into()..10.into()),
+    ///     ],
+    /// );
+    /// ```
+    pub fn split_at_each<I: IntoIterator<Item = StrIndex>>(
+        self,
+        points: I,
+    ) -> impl Iterator<Item = StrRange> {
+        let mut points = points.into_iter();
+        let mut cursor = Some(self.start());
+        core::iter::from_fn(move || {
+            let start = cursor?;
+            match points.next() {
+                Some(point) => {
+                    assert!(
+                        start <= point && point <= self.end(),
+                        "split point {} out of order or outside of string range {}",
+                        point,
+                        self,
+                    );
+                    cursor = Some(point);
+                    Some(StrRange::from(start..point))
+                }
+                None => {
+                    cursor = None;
+                    Some(StrRange::from(start..self.end()))
+                }
+            }
+        })
+    }
+
+    /// The subranges of this range of length `len`, each starting `step` after the previous.
+    ///
+    /// With `step == len` these are the chunks of this range,
+    /// and with `step == 1` these are its windows.
+    /// A final subrange shorter than `len` is not yielded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` or `step` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// let range = StrRange::from(0.into()..10.into());
+    /// assert_eq!(
+    ///     range.subranges(4.into(), 4.into()).collect::<Vec<_>>(),
+    ///     vec![
+    ///         StrRange::from(0.into()..4.into()),
+    ///         StrRange::from(4.into()..8.into()),
+    ///     ],
+    /// );
+    ///
+    /// let range = StrRange::from(0.into()..5.into());
+    /// assert_eq!(
+    ///     range.subranges(3.into(), 1.into()).collect::<Vec<_>>(),
+    ///     vec![
+    ///         StrRange::from(0.into()..3.into()),
+    ///         StrRange::from(1.into()..4.into()),
+    ///         StrRange::from(2.into()..5.into()),
+    ///     ],
+    /// );
+    /// ```
+    pub fn subranges(self, len: StrIndex, step: StrIndex) -> impl Iterator<Item = StrRange> {
+        assert!(len.raw != 0, "subrange length must be nonzero");
+        assert!(step.raw != 0, "subrange step must be nonzero");
+        let mut cursor = Some(self.start());
+        core::iter::from_fn(move || {
+            let start = cursor?;
+            let end = start.checked_add(len).filter(|&end| end <= self.end())?;
+            cursor = start.checked_add(step);
+            Some(StrRange::from(start..end))
+        })
+    }
+
+    /// Evenly spaced positions across this range, as for a ruler.
+    ///
+    /// Yields `start`, `start + step`, and so on, always finishing with `end` exactly once
+    /// whether or not it lands on a step.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// let range = StrRange::from(0.into()..10.into());
+    /// assert_eq!(
+    ///     range.ticks(4.into()).map(u32::from).collect::<Vec<_>>(),
+    ///     vec![0, 4, 8, 10],
+    /// );
+    /// assert_eq!(
+    ///     range.ticks(5.into()).map(u32::from).collect::<Vec<_>>(),
+    ///     vec![0, 5, 10],
+    /// );
+    /// ```
+    pub fn ticks(self, step: StrIndex) -> impl Iterator<Item = StrIndex> {
+        assert!(step.raw != 0, "tick step must be nonzero");
+        let mut cursor = Some(self.start());
+        core::iter::from_fn(move || {
+            let tick = cursor?;
+            cursor = if tick < self.end() {
+                Some(
+                    tick.checked_add(step)
+                        .map_or(self.end(), |next| cmp::min(next, self.end())),
+                )
+            } else {
+                None
+            };
+            Some(tick)
+        })
+    }
+
+    /// Extend this range to include `index`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// let mut range = StrIndex::from(10).as_unit_range();
+    /// range.cover_point(5.into());
+    /// range.cover_point(20.into());
+    /// range.cover_point(15.into());
+    /// assert_eq!(range, StrRange::from(5.into()..20.into()));
+    /// ```
+    pub fn cover_point(&mut self, index: StrIndex) {
+        self.start = cmp::min(self.start, index);
+        self.end = cmp::max(self.end, index);
+    }
+
+    /// Extend this range to cover `other`, as by [`merge`](StrRange::merge).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// let mut range = StrRange::from(10.into()..20.into());
+    /// range.cover_range(StrRange::from(0.into()..5.into()));
+    /// range.cover_range(StrRange::from(12.into()..15.into()));
+    /// assert_eq!(range, StrRange::from(0.into()..20.into()));
+    /// range.cover_range(StrRange::from(25.into()..30.into()));
+    /// assert_eq!(range, StrRange::from(0.into()..30.into()));
+    /// ```
+    pub fn cover_range(&mut self, other: StrRange) {
+        *self = self.merge(other);
+    }
+
+    /// This range as a string which sorts lexically as the range sorts.
+    ///
+    /// `Display` (`start..end`) does not sort lexically like the range does,
+    /// as e.g. `"10..20" < "9..10"`. This zero-pads both indices to the full width of a `u32`.
+    /// The key string can be parsed back into a `StrRange`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// let range = StrRange::from(5.into()..10.into());
+    /// assert_eq!(range.to_key_string(), "0000000005..0000000010");
+    /// assert_eq!(range.to_key_string().parse(), Ok(range));
+    ///
+    /// let mut ranges = vec![
+    ///     StrRange::from(9.into()..10.into()),
+    ///     StrRange::from(10.into()..20.into()),
+    ///     StrRange::from(9.into()..100.into()),
+    ///     StrRange::from(0.into()..1000.into()),
+    /// ];
+    /// let mut keys: Vec<String> = ranges.iter().map(|r| r.to_key_string()).collect();
+    /// ranges.sort();
+    /// keys.sort();
+    /// assert_eq!(keys, ranges.iter().map(|r| r.to_key_string()).collect::<Vec<_>>());
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn to_key_string(self) -> alloc::string::String {
+        alloc::format!("{:010}..{:010}", self.start().raw, self.end().raw)
+    }
+
+    /// Display this range as an inclusive range, `start..=last`.
+    ///
+    /// Returns `None` for an empty range, which has no last index.
+    /// The inclusive form can be parsed back into a `StrRange`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// let range = StrRange::from(0.into()..10.into());
+    /// let inclusive = range.display_inclusive().unwrap().to_string();
+    /// assert_eq!(inclusive, "0..=9");
+    /// assert_eq!(inclusive.parse(), Ok(range));
+    /// assert!(StrIndex::from(5).as_unit_range().display_inclusive().is_none());
+    /// ```
+    pub fn display_inclusive(self) -> Option<impl core::fmt::Display> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(fmt::DisplayInclusive(self))
+        }
+    }
+
+    /// Debug format this range in its most compact form, `start..end`.
+    ///
+    /// Unlike the standard `Debug`, formatter flags (such as `{:#?}`) are ignored.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// let range = StrIndex::from(5).as_unit_range();
+    /// assert_eq!(format!("{:#?}", range), "5..5 (empty)");
+    /// assert_eq!(format!("{:#?}", range.debug_compact()), "5..5");
+    /// assert_eq!(format!("{:08x?}", range.debug_compact()), "5..5");
+    /// ```
+    pub fn debug_compact(self) -> impl core::fmt::Debug {
+        fmt::DebugCompact(self)
+    }
+
+    /// Display this range followed by its length.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// let range = StrRange::from(5.into()..15.into());
+    /// assert_eq!(range.display_with_len().to_string(), "5..15 [10]");
+    /// ```
+    pub fn display_with_len(self) -> impl core::fmt::Display {
+        fmt::DisplayWithLen(self)
+    }
+
+    /// Every byte index within this range, excluding `self.end()`.
+    ///
+    /// The iterator's `len()` is always exactly the remaining length of the range.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// let range = StrRange::from(2.into()..5.into());
+    /// let mut indices = range.indices();
+    /// assert_eq!(indices.len(), range.len().to_usize());
+    /// assert_eq!(indices.next_back(), Some(4.into()));
+    /// assert_eq!(indices.map(u32::from).collect::<Vec<_>>(), vec![2, 3]);
+    /// ```
+    pub fn indices(self) -> StrRangeIndices {
+        self.start().step_range(self.end())
+    }
+
+    /// Every position a cursor can occupy within this range.
+    ///
+    /// Note that this includes `self.end()`;
+    /// a range of length `n` has `n + 1` positions, but only `n` bytes.
+    /// See [`indices`] for just the byte indices.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// let range = StrRange::from(0.into()..3.into());
+    /// assert_eq!(
+    ///     range.positions().map(u32::from).collect::<Vec<_>>(),
+    ///     vec![0, 1, 2, 3],
+    /// );
+    /// ```
+    ///
+    /// [`indices`]: StrRange::indices
+    pub fn positions(self) -> impl Iterator<Item = StrIndex> {
+        (self.start().raw..=self.end().raw).map(StrIndex::from)
+    }
+
+    /// This range as a `Range<StrIndex>`,
+    /// e.g. for looking up `map.range(range.to_btree_range())` in a `BTreeMap<StrIndex, _>`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// let range = StrRange::from(5.into()..10.into());
+    /// assert_eq!(range.to_btree_range(), StrIndex::from(5)..StrIndex::from(10));
+    /// ```
+    pub fn to_btree_range(self) -> Range<StrIndex> {
+        self.start()..self.end()
+    }
+
+    /// The range from the start of the string to the end of this range.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// let range = StrRange::from(5.into()..10.into());
+    /// assert_eq!(range.to_range_to(), ..StrIndex::from(10));
+    /// ```
+    pub fn to_range_to(self) -> RangeTo<StrIndex> {
+        ..self.end()
+    }
+
+    /// The range from the start of this range to the end of the string.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// let range = StrRange::from(5.into()..10.into());
+    /// assert_eq!(range.to_range_from(), StrIndex::from(5)..);
+    /// ```
+    pub fn to_range_from(self) -> RangeFrom<StrIndex> {
+        self.start()..
+    }
+
+    /// A seek to the start of this range.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// # use std::io::{Cursor, Read, Seek};
+    /// let mut file = Cursor::new("swordfish");
+    /// let range = StrRange::from(5.into()..9.into());
+    /// file.seek(range.seek_start()).unwrap();
+    /// let mut buf = String::new();
+    /// file.read_to_string(&mut buf).unwrap();
+    /// assert_eq!(buf, "fish");
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn seek_start(self) -> std::io::SeekFrom {
+        std::io::SeekFrom::Start(self.start().raw.into())
+    }
+
+    /// A seek to the end of this range.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// # use std::io::{Cursor, Seek};
+    /// let mut file = Cursor::new("swordfish");
+    /// let range = StrRange::from(0.into()..5.into());
+    /// assert_eq!(file.seek(range.seek_end_from()).unwrap(), 5);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn seek_end_from(self) -> std::io::SeekFrom {
+        std::io::SeekFrom::Start(self.end().raw.into())
+    }
+
+    /// Assert that this range is valid for indexing `s`.
+    ///
+    /// # Panics
+    ///
+    /// Panics with a descriptive message if this range is out of bounds of `s`
+    /// or either endpoint is not on a char boundary.
+    #[cfg(any(test, feature = "test-util"))]
+    pub fn assert_valid_for(self, s: &str) {
+        assert!(self.start() <= self.end(), "invalid string range {}", self);
+        assert!(
+            self.end().to_usize() <= s.len(),
+            "string range {} out of bounds of string of length {}",
+            self,
+            s.len(),
+        );
+        assert!(
+            s.is_char_boundary(self.start().to_usize()),
+            "string range {} starts inside a char of {:?}",
+            self,
+            s,
+        );
+        assert!(
+            s.is_char_boundary(self.end().to_usize()),
+            "string range {} ends inside a char of {:?}",
+            self,
+            s,
+        );
+    }
+}
+
+/// Coalesce ranges, merging any that overlap or touch.
+///
+/// The input must be sorted by start index (as e.g. by `StrRange`'s `Ord`);
+/// an unsorted input will not be fully coalesced.
+///
+/// # Examples
+///
+/// ```rust
+/// # use str_index::*;
+/// let ranges = vec![
+///     StrRange::from(0.into()..10.into()),
+///     StrRange::from(5.into()..15.into()),
+///     StrRange::from(15.into()..20.into()),
+/// ];
+/// assert_eq!(
+///     coalesce(ranges).collect::<Vec<_>>(),
+///     vec![StrRange::from(0.into()..20.into())],
+/// );
+///
+/// let ranges = vec![
+///     StrRange::from(0.into()..5.into()),
+///     StrRange::from(5.into()..10.into()),
+///     StrRange::from(15.into()..20.into()),
+///     StrRange::from(25.into()..30.into()),
+///     StrRange::from(26.into()..28.into()),
+/// ];
+/// assert_eq!(
+///     coalesce(ranges).collect::<Vec<_>>(),
+///     vec![
+///         StrRange::from(0.into()..10.into()),
+///         StrRange::from(15.into()..20.into()),
+///         StrRange::from(25.into()..30.into()),
+///     ],
+/// );
+/// ```
+pub fn coalesce<I: IntoIterator<Item = StrRange>>(ranges: I) -> impl Iterator<Item = StrRange> {
+    let mut ranges = ranges.into_iter().peekable();
+    core::iter::from_fn(move || {
+        let mut run = ranges.next()?;
+        while let Some(next) = ranges.next_if(|next| next.start() <= run.end()) {
+            run = run.merge(next);
+        }
+        Some(run)
+    })
+}
+
+/// The sum of the lengths of these ranges.
+///
+/// Overlapping ranges are counted multiple times; see [`covered_len`] to count them once.
+///
+/// # Panics
+///
+/// Panics if the total length overflows a `StrIndex`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use str_index::*;
+/// let ranges = vec![
+///     StrRange::from(0.into()..10.into()),
+///     StrRange::from(5.into()..15.into()),
+///     StrRange::from(20.into()..25.into()),
+/// ];
+/// assert_eq!(total_len(ranges), StrIndex::from(25));
+/// ```
+pub fn total_len<I: IntoIterator<Item = StrRange>>(ranges: I) -> StrIndex {
+    ranges.into_iter().fold(StrIndex::from(0), |total, range| {
+        total
+            .checked_add(range.len())
+            .expect("string index too large")
+    })
+}
+
+/// The number of bytes covered by any of these ranges.
+///
+/// Unlike [`total_len`], overlapping ranges are only counted once.
+/// As with [`coalesce`], the input must be sorted by start index.
+///
+/// # Examples
+///
+/// ```rust
+/// # use str_index::*;
+/// let ranges = vec![
+///     StrRange::from(0.into()..10.into()),
+///     StrRange::from(5.into()..15.into()),
+///     StrRange::from(20.into()..25.into()),
+/// ];
+/// assert_eq!(covered_len(ranges), StrIndex::from(20));
+///
+/// let disjoint = vec![
+///     StrRange::from(0.into()..10.into()),
+///     StrRange::from(20.into()..25.into()),
+/// ];
+/// assert_eq!(covered_len(disjoint.clone()), total_len(disjoint));
+/// ```
+pub fn covered_len<I: IntoIterator<Item = StrRange>>(ranges: I) -> StrIndex {
+    total_len(coalesce(ranges))
+}
+
+/// Replace `range` of `string` with `replacement`, returning the range of the inserted text.
+///
+/// # Panics
+///
+/// Panics if `range` is out of bounds or not on char boundaries of `string`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use str_index::*;
+/// let mut s = String::from("swordfish");
+/// let range = splice(&mut s, StrRange::from(0.into()..5.into()), "cat");
+/// assert_eq!(s, "catfish");
+/// assert_eq!(range, StrRange::from(0.into()..3.into()));
+///
+/// let range = splice(&mut s, StrRange::from(3.into()..7.into()), "erpillar");
+/// assert_eq!(s, "caterpillar");
+/// assert_eq!(range, StrRange::from(3.into()..11.into()));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn splice(string: &mut alloc::string::String, range: StrRange, replacement: &str) -> StrRange {
+    string.replace_range(
+        range.start().to_usize()..range.end().to_usize(),
+        replacement,
+    );
+    range.start().range_for(StrIndex::from_str_len(replacement))
+}
+
+/// The entries of `map` whose key is within `range`.
+///
+/// # Example
+///
+/// ```rust
+/// # use str_index::*;
+/// # use std::collections::BTreeMap;
+/// let map: BTreeMap<StrIndex, &str> = vec![
+///     (0.into(), "zero"),
+///     (5.into(), "five"),
+///     (9.into(), "nine"),
+///     (10.into(), "ten"),
+/// ].into_iter().collect();
+/// let range = StrRange::from(5.into()..10.into());
+/// assert_eq!(
+///     query(&map, range).map(|(_, &v)| v).collect::<Vec<_>>(),
+///     vec!["five", "nine"],
+/// );
+/// ```
+#[cfg(feature = "alloc")]
+pub fn query<V>(
+    map: &alloc::collections::BTreeMap<StrIndex, V>,
+    range: StrRange,
+) -> impl Iterator<Item = (&StrIndex, &V)> {
+    map.range(range.to_btree_range())
+}
+
+/// The index of the start of each line of `s`.
+///
+/// Lines are terminated by `\n` (including `\r\n`).
+/// The first line always starts at `0`, and a trailing line terminator begins an empty final line.
+///
+/// # Examples
+///
+/// ```rust
+/// # use str_index::*;
+/// assert_eq!(
+///     line_starts("fn main() {\r\n}"),
+///     vec![StrIndex::from(0), StrIndex::from(13)],
+/// );
+/// assert_eq!(
+///     line_starts("a\nb\n"),
+///     vec![StrIndex::from(0), StrIndex::from(2), StrIndex::from(4)],
+/// );
+/// assert_eq!(line_starts(""), vec![StrIndex::from(0)]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn line_starts(s: &str) -> alloc::vec::Vec<StrIndex> {
+    let _ = StrIndex::from_str_len(s); // assert that all indices fit in `u32`
+    let mut starts = alloc::vec![StrIndex::from(0)];
+    starts.extend(
+        s.match_indices('\n')
+            .map(|(i, _)| StrIndex::from(i as u32 + 1)),
+    );
+    starts
+}
+
+/// A visualization of which of the first `total` bytes are covered by any of `ranges`,
+/// with `#` for covered bytes and `-` for uncovered bytes.
+///
+/// This is intended as a debugging aid, e.g. for test output.
+///
+/// # Example
+///
+/// ```rust
+/// # use str_index::*;
+/// let ranges = [
+///     StrRange::from(1.into()..3.into()),
+///     StrRange::from(5.into()..6.into()),
+///     StrRange::from(8.into()..12.into()),
+/// ];
+/// assert_eq!(coverage_string(&ranges, 10.into()), "-##--#--##");
+/// ```
+#[cfg(feature = "alloc")]
+pub fn coverage_string(ranges: &[StrRange], total: StrIndex) -> alloc::string::String {
+    StrIndex::from(0)
+        .step_range(total)
+        .map(|index| {
+            if ranges.iter().any(|range| range.contains_exclusive(index)) {
+                '#'
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+#[test]
+#[cfg_attr(
+    debug_assertions,
+    should_panic(expected = "invalid string range 10..0")
+)]
+fn inverted_range_len() {
+    let range = StrRange {
+        start: 10.into(),
+        end: 0.into(),
+    };
+    assert_eq!(range.len(), StrIndex::from(0));
+}
+
+#[test]
+fn assert_valid_for() {
+    StrRange::from(0.into()..12.into()).assert_valid_for("メカジキ");
+    StrRange::from(3.into()..6.into()).assert_valid_for("メカジキ");
+    StrIndex::from(0).as_unit_range().assert_valid_for("");
+}
+
+#[test]
+#[should_panic(expected = "string range 0..13 out of bounds of string of length 12")]
+fn assert_valid_for_out_of_bounds() {
+    StrRange::from(0.into()..13.into()).assert_valid_for("メカジキ");
+}
+
+#[test]
+#[should_panic(expected = "string range 1..3 starts inside a char")]
+fn assert_valid_for_mid_char_start() {
+    StrRange::from(1.into()..3.into()).assert_valid_for("メカジキ");
+}
+
+#[test]
+#[should_panic(expected = "string range 0..4 ends inside a char")]
+fn assert_valid_for_mid_char_end() {
+    StrRange::from(0.into()..4.into()).assert_valid_for("メカジキ");
+}
+
+#[test]
+fn hash_is_single_write_u32() {
+    #[derive(Default)]
+    struct U32Hasher(Option<u32>);
+
+    impl Hasher for U32Hasher {
+        fn finish(&self) -> u64 {
+            u64::from(self.0.expect("nothing hashed"))
+        }
+
+        fn write(&mut self, _: &[u8]) {
+            panic!("unexpected write");
+        }
+
+        fn write_u32(&mut self, i: u32) {
+            assert!(self.0.is_none(), "unexpected second write");
+            self.0 = Some(i);
+        }
+    }
+
+    let mut hasher = U32Hasher::default();
+    StrIndex::from(42).hash(&mut hasher);
+    assert_eq!(hasher.finish(), 42);
+}
+
+#[test]
+fn normalize_inverted_range() {
+    let range = StrRange {
+        start: 10.into(),
+        end: 0.into(),
+    };
+    assert_eq!(range.normalize(), StrRange::from(0.into()..10.into()));
+}
+
+#[test]
+fn layout() {
+    assert_eq!(mem::size_of::<StrIndex>(), 4);
+    assert_eq!(mem::align_of::<StrIndex>(), 4);
+    assert_eq!(mem::size_of::<StrRange>(), 8);
+    assert_eq!(mem::align_of::<StrRange>(), 4);
+}
+
+#[test]
+fn nonzero_niche() {
+    assert_eq!(mem::size_of::<StrIndexNonZero>(), 4);
+    assert_eq!(mem::size_of::<Option<StrIndexNonZero>>(), 4);
+}
+
+#[test]
+#[cfg(target_pointer_width = "64")]
+fn saturating_from_oversized_len() {
+    let max = StrIndex::from(u32::MAX);
+    assert_eq!(StrIndex::saturating_from_len(u32::MAX as usize), max);
+    assert_eq!(StrIndex::saturating_from_len(u32::MAX as usize + 1), max);
+    assert_eq!(StrIndex::saturating_from_len(usize::MAX), max);
+}
+
+#[test]
+fn const_from_u32() {
+    const INDEX: StrIndex = StrIndex::from_u32(42);
+    const TABLE: [StrIndex; 2] = [StrIndex::from_u32(0), INDEX];
+    assert_eq!(TABLE, [StrIndex::from(0), StrIndex::from(42)]);
 }
@@ -0,0 +1,30 @@
+use {
+    crate::{ext::floor_char_boundary, StrIndex, StrRange},
+    core::cmp,
+    proptest::strategy::Strategy,
+};
+
+impl StrRange {
+    /// A strategy generating ranges which are valid for indexing `s`.
+    ///
+    /// Both endpoints are on char boundaries and within `s`,
+    /// so property tests can slice `s` with the generated ranges without panicking.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use str_index::*;
+    /// # use proptest::prelude::*;
+    /// let s = "メカジキ";
+    /// proptest!(|(range in StrRange::strategy_within(s))| {
+    ///     let _ = &s[range];
+    /// });
+    /// ```
+    pub fn strategy_within(s: &str) -> impl Strategy<Value = StrRange> + '_ {
+        (0..=s.len(), 0..=s.len()).prop_map(move |(a, b)| {
+            let a = StrIndex::from_str_len(&s[..floor_char_boundary(s, a)]);
+            let b = StrIndex::from_str_len(&s[..floor_char_boundary(s, b)]);
+            StrRange::from(cmp::min(a, b)..cmp::max(a, b))
+        })
+    }
+}
@@ -1,8 +1,11 @@
 use {
     super::{StrIndex, StrRange},
-    core::fmt,
+    core::{convert::TryFrom, fmt},
     serde::{
-        de::{Deserialize, Deserializer, Error, MapAccess, SeqAccess, Visitor},
+        de::{
+            Deserialize, DeserializeSeed, Deserializer, Error, MapAccess, SeqAccess, Unexpected,
+            Visitor,
+        },
         ser::{Serialize, SerializeStruct, Serializer},
     },
 };
@@ -42,7 +45,66 @@ impl<'de> Deserialize<'de> for StrRange {
     where
         D: Deserializer<'de>,
     {
-        deserializer.deserialize_struct("StrRange", STR_RANGE_FIELDS, StrRangeVisitor)
+        // Human-readable formats may also spell an empty range `n..n` as a bare `n`.
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(StrRangeVisitor)
+        } else {
+            deserializer.deserialize_struct("StrRange", STR_RANGE_FIELDS, StrRangeVisitor)
+        }
+    }
+}
+
+impl StrIndex {
+    /// Deserialize a `StrIndex`, failing if it is greater than `max`.
+    ///
+    /// This is useful to reject out-of-bounds indices from untrusted input
+    /// when the length of the indexed string is known;
+    /// see [`BoundedStrIndex`] for the same as a [`DeserializeSeed`].
+    pub fn deserialize_bounded<'de, D>(deserializer: D, max: StrIndex) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let index = StrIndex::deserialize(deserializer)?;
+        if index > max {
+            Err(Error::custom(format_args!(
+                "string index {} out of bounds (maximum {})",
+                index, max
+            )))
+        } else {
+            Ok(index)
+        }
+    }
+}
+
+/// A [`DeserializeSeed`] for a [`StrIndex`] no greater than `max`.
+///
+/// This is [`StrIndex::deserialize_bounded`] in a form which can be passed around,
+/// e.g. to [`SeqAccess::next_element_seed`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use {serde::de::DeserializeSeed, str_index::*};
+/// let seed = BoundedStrIndex { max: 10.into() };
+/// let json = serde_json::Value::from(5);
+/// assert_eq!(seed.deserialize(json).unwrap(), StrIndex::from(5));
+/// let json = serde_json::Value::from(11);
+/// assert!(seed.deserialize(json).is_err());
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct BoundedStrIndex {
+    /// The greatest index to accept.
+    pub max: StrIndex,
+}
+
+impl<'de> DeserializeSeed<'de> for BoundedStrIndex {
+    type Value = StrIndex;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        StrIndex::deserialize_bounded(deserializer, self.max)
     }
 }
 
@@ -62,6 +124,16 @@ impl<'de> Visitor<'de> for StrIndexVisitor {
         Ok(StrIndex::from(v))
     }
 
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        match u32::try_from(v) {
+            Ok(v) => Ok(StrIndex::from(v)),
+            Err(_) => Err(Error::invalid_value(Unexpected::Unsigned(v), &self)),
+        }
+    }
+
     fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
     where
         D: Deserializer<'de>,
@@ -76,7 +148,24 @@ impl<'de> Visitor<'de> for StrRangeVisitor {
     type Value = StrRange;
 
     fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "struct StrRange")
+        write!(f, "struct StrRange or u32")
+    }
+
+    fn visit_u32<E>(self, v: u32) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        Ok(StrIndex::from(v).as_unit_range())
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        match u32::try_from(v) {
+            Ok(v) => self.visit_u32(v),
+            Err(_) => Err(Error::invalid_value(Unexpected::Unsigned(v), &self)),
+        }
     }
 
     fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
@@ -91,15 +180,8 @@ impl<'de> Visitor<'de> for StrRangeVisitor {
             Some(it) => it,
             None => return Err(Error::invalid_length(1, &self)),
         };
-        let range = StrRange { start, end }; // construct manually to bypass ordering assert!
-        if start > end {
-            Err(Error::custom(format_args!(
-                "invalid string range {}",
-                range
-            )))
-        } else {
-            Ok(range)
-        }
+        StrRange::try_new(start, end)
+            .ok_or_else(|| Error::custom(format_args!("invalid string range {}..{}", start, end)))
     }
 
     fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
@@ -134,15 +216,8 @@ impl<'de> Visitor<'de> for StrRangeVisitor {
             Some(it) => it,
             None => return Err(Error::missing_field("end")),
         };
-        let range = StrRange { start, end }; // construct manually to bypass ordering assert!
-        if start > end {
-            Err(Error::custom(format_args!(
-                "invalid string range {}",
-                range
-            )))
-        } else {
-            Ok(range)
-        }
+        StrRange::try_new(start, end)
+            .ok_or_else(|| Error::custom(format_args!("invalid string range {}..{}", start, end)))
     }
 }
 
@@ -158,27 +233,36 @@ impl<'de> Deserialize<'de> for StrRangeField {
     where
         D: Deserializer<'de>,
     {
-        deserializer.deserialize_identifier(StrRangeFieldVisitor)
+        match deserializer.deserialize_identifier(FieldVisitor(STR_RANGE_FIELDS))? {
+            0 => Ok(StrRangeField::Start),
+            _ => Ok(StrRangeField::End),
+        }
     }
 }
 
-struct StrRangeFieldVisitor;
+/// Visits a struct field identifier, producing its index in the given field names.
+pub(crate) struct FieldVisitor(pub(crate) &'static [&'static str]);
 
-impl<'de> Visitor<'de> for StrRangeFieldVisitor {
-    type Value = StrRangeField;
+impl<'de> Visitor<'de> for FieldVisitor {
+    type Value = usize;
 
-    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-        formatter.write_str("`start` or `end`")
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, field) in self.0.iter().enumerate() {
+            if i != 0 {
+                f.write_str(" or ")?;
+            }
+            write!(f, "`{}`", field)?;
+        }
+        Ok(())
     }
 
     fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
     where
         E: Error,
     {
-        match value {
-            "start" => Ok(StrRangeField::Start),
-            "end" => Ok(StrRangeField::End),
-            _ => Err(Error::unknown_field(value, STR_RANGE_FIELDS)),
+        match self.0.iter().position(|&field| field == value) {
+            Some(index) => Ok(index),
+            None => Err(Error::unknown_field(value, self.0)),
         }
     }
 
@@ -186,13 +270,82 @@ impl<'de> Visitor<'de> for StrRangeFieldVisitor {
     where
         E: Error,
     {
-        match value {
-            b"start" => Ok(StrRangeField::Start),
-            b"end" => Ok(StrRangeField::End),
-            _ => {
-                let value = serde::export::from_utf8_lossy(value);
-                Err(Error::unknown_field(&value, STR_RANGE_FIELDS))
+        match self.0.iter().position(|field| field.as_bytes() == value) {
+            Some(index) => Ok(index),
+            None => {
+                // serde's own lossy conversion is private, so mirror it here.
+                #[cfg(feature = "alloc")]
+                let value: &str = &alloc::string::String::from_utf8_lossy(value);
+                #[cfg(not(feature = "alloc"))]
+                let value = core::str::from_utf8(value).unwrap_or("\u{fffd}\u{fffd}\u{fffd}");
+                Err(Error::unknown_field(value, self.0))
             }
         }
     }
 }
+
+/// A [`StrRange`] which serializes as a single packed `u64`.
+///
+/// The start index is stored in the high 32 bits and the end index in the low 32 bits,
+/// i.e. `(start as u64) << 32 | end as u64`.
+/// Deserialization fails if the unpacked start is greater than the unpacked end.
+#[derive(Copy, Clone, Default, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct PackedStrRange(pub StrRange);
+
+impl fmt::Debug for PackedStrRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl From<StrRange> for PackedStrRange {
+    fn from(range: StrRange) -> Self {
+        PackedStrRange(range)
+    }
+}
+
+impl From<PackedStrRange> for StrRange {
+    fn from(packed: PackedStrRange) -> Self {
+        packed.0
+    }
+}
+
+impl Serialize for PackedStrRange {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let StrRange { start, end } = self.0;
+        serializer.serialize_u64(u64::from(start.raw) << 32 | u64::from(end.raw))
+    }
+}
+
+impl<'de> Deserialize<'de> for PackedStrRange {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_u64(PackedStrRangeVisitor)
+    }
+}
+
+struct PackedStrRangeVisitor;
+
+impl<'de> Visitor<'de> for PackedStrRangeVisitor {
+    type Value = PackedStrRange;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "u64")
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        let start = StrIndex::from((v >> 32) as u32);
+        let end = StrIndex::from(v as u32);
+        StrRange::try_new(start, end)
+            .map(PackedStrRange)
+            .ok_or_else(|| Error::custom(format_args!("invalid string range {}..{}", start, end)))
+    }
+}
@@ -0,0 +1,220 @@
+//! Alternate serde representations of [`StrRange`](crate::StrRange),
+//! for use with `#[serde(with = "...")]` and friends.
+
+/// Serialize a `StrRange` as an inclusive range, `{ start, end_inclusive }`.
+///
+/// Empty ranges cannot be represented inclusively and fail to serialize.
+///
+/// # Example
+///
+/// ```rust
+/// # use serde::{Deserialize, Serialize};
+/// # use str_index::StrRange;
+/// #[derive(Serialize, Deserialize)]
+/// struct Span {
+///     #[serde(with = "str_index::serde_repr::inclusive")]
+///     range: StrRange,
+/// }
+/// ```
+pub mod inclusive {
+    use {
+        crate::{serde::FieldVisitor, StrIndex, StrRange},
+        core::fmt,
+        serde::{
+            de::{Deserialize, Deserializer, Error, MapAccess, SeqAccess, Visitor},
+            ser::{self, SerializeStruct, Serializer},
+        },
+    };
+
+    /// Serialize a `StrRange` as `{ start, end_inclusive }`.
+    ///
+    /// Empty ranges cannot be represented inclusively and fail to serialize.
+    pub fn serialize<S>(range: &StrRange, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if range.is_empty() {
+            return Err(ser::Error::custom(format_args!(
+                "empty string range {} cannot be represented inclusively",
+                range
+            )));
+        }
+        let mut s = serializer.serialize_struct("StrRangeInclusive", 2)?;
+        s.serialize_field("start", &range.start)?;
+        s.serialize_field("end_inclusive", &(range.end - StrIndex::from(1)))?;
+        s.end()
+    }
+
+    /// Deserialize a `StrRange` from `{ start, end_inclusive }`.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<StrRange, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct("StrRangeInclusive", INCLUSIVE_FIELDS, InclusiveVisitor)
+    }
+
+    fn from_inclusive<E: Error>(start: StrIndex, end_inclusive: StrIndex) -> Result<StrRange, E> {
+        if start > end_inclusive {
+            return Err(Error::custom(format_args!(
+                "invalid string range {}..={}",
+                start, end_inclusive
+            )));
+        }
+        match end_inclusive.checked_add(1.into()) {
+            Some(end) => Ok(StrRange::from(start..end)),
+            None => Err(Error::custom(format_args!(
+                "string range {}..={} too large",
+                start, end_inclusive
+            ))),
+        }
+    }
+
+    struct InclusiveVisitor;
+
+    impl<'de> Visitor<'de> for InclusiveVisitor {
+        type Value = StrRange;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "struct StrRangeInclusive")
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            let start: StrIndex = match seq.next_element()? {
+                Some(it) => it,
+                None => return Err(Error::invalid_length(0, &self)),
+            };
+            let end_inclusive: StrIndex = match seq.next_element()? {
+                Some(it) => it,
+                None => return Err(Error::invalid_length(1, &self)),
+            };
+            from_inclusive(start, end_inclusive)
+        }
+
+        fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+        where
+            A: MapAccess<'de>,
+        {
+            let mut start: Option<StrIndex> = None;
+            let mut end_inclusive: Option<StrIndex> = None;
+            while let Some(key) = map.next_key()? {
+                match key {
+                    InclusiveField::Start => {
+                        if start.is_some() {
+                            return Err(Error::duplicate_field("start"));
+                        } else {
+                            start = Some(map.next_value()?)
+                        }
+                    }
+                    InclusiveField::EndInclusive => {
+                        if end_inclusive.is_some() {
+                            return Err(Error::duplicate_field("end_inclusive"));
+                        } else {
+                            end_inclusive = Some(map.next_value()?)
+                        }
+                    }
+                }
+            }
+            let start = match start {
+                Some(it) => it,
+                None => return Err(Error::missing_field("start")),
+            };
+            let end_inclusive = match end_inclusive {
+                Some(it) => it,
+                None => return Err(Error::missing_field("end_inclusive")),
+            };
+            from_inclusive(start, end_inclusive)
+        }
+    }
+
+    const INCLUSIVE_FIELDS: &[&str] = &["start", "end_inclusive"];
+
+    enum InclusiveField {
+        Start,
+        EndInclusive,
+    }
+
+    impl<'de> Deserialize<'de> for InclusiveField {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            match deserializer.deserialize_identifier(FieldVisitor(INCLUSIVE_FIELDS))? {
+                0 => Ok(InclusiveField::Start),
+                _ => Ok(InclusiveField::EndInclusive),
+            }
+        }
+    }
+}
+
+/// Serialize a `StrRange` as an array, `[start, end]`.
+///
+/// # Example
+///
+/// ```rust
+/// # use serde::{Deserialize, Serialize};
+/// # use str_index::StrRange;
+/// #[derive(Serialize, Deserialize)]
+/// struct Span {
+///     #[serde(with = "str_index::serde_repr::as_array")]
+///     range: StrRange,
+/// }
+/// ```
+pub mod as_array {
+    use {
+        crate::{StrIndex, StrRange},
+        core::fmt,
+        serde::{
+            de::{Deserializer, Error, SeqAccess, Visitor},
+            ser::{SerializeTuple, Serializer},
+        },
+    };
+
+    /// Serialize a `StrRange` as `[start, end]`.
+    pub fn serialize<S>(range: &StrRange, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_tuple(2)?;
+        s.serialize_element(&range.start)?;
+        s.serialize_element(&range.end)?;
+        s.end()
+    }
+
+    /// Deserialize a `StrRange` from `[start, end]`.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<StrRange, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_tuple(2, ArrayVisitor)
+    }
+
+    struct ArrayVisitor;
+
+    impl<'de> Visitor<'de> for ArrayVisitor {
+        type Value = StrRange;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "an array of two string indices")
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            let start: StrIndex = match seq.next_element()? {
+                Some(it) => it,
+                None => return Err(Error::invalid_length(0, &self)),
+            };
+            let end: StrIndex = match seq.next_element()? {
+                Some(it) => it,
+                None => return Err(Error::invalid_length(1, &self)),
+            };
+            StrRange::try_new(start, end).ok_or_else(|| {
+                Error::custom(format_args!("invalid string range {}..{}", start, end))
+            })
+        }
+    }
+}
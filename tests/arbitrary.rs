@@ -0,0 +1,18 @@
+use arbitrary::Unstructured;
+use str_index::StrRange;
+
+#[test]
+fn arbitrary_valid_for() {
+    // one-, two-, three- and four-byte chars, so most arbitrary offsets land mid-char
+    let strings = ["", "a", "añb", "メカジキ", "🐟🐠"];
+    let data: Vec<u8> = (0..=255).chain((0..=255).rev()).collect();
+    for s in &strings {
+        for offset in 0..data.len() {
+            let mut u = Unstructured::new(&data[offset..]);
+            let range = StrRange::arbitrary_valid_for(&mut u, s).unwrap();
+            assert!(s
+                .get(range.start().to_usize()..range.end().to_usize())
+                .is_some());
+        }
+    }
+}
@@ -0,0 +1,9 @@
+use str_index::{StrIndex, StrRange};
+
+fn assert_format<T: defmt::Format>() {}
+
+#[test]
+fn format() {
+    assert_format::<StrIndex>();
+    assert_format::<StrRange>();
+}
@@ -0,0 +1,19 @@
+use proptest::prelude::*;
+use str_index::StrRange;
+
+// one- to four-byte chars and a combining mark
+const TEXT: &str = "ab\u{301}ñメ🐟\r\nz";
+
+proptest! {
+    #[test]
+    fn strategy_within(range in StrRange::strategy_within(TEXT)) {
+        prop_assert!(TEXT
+            .get(range.start().to_usize()..range.end().to_usize())
+            .is_some());
+    }
+
+    #[test]
+    fn strategy_within_empty(range in StrRange::strategy_within("")) {
+        prop_assert!(range.is_empty());
+    }
+}
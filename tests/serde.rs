@@ -1,5 +1,9 @@
-use serde_test::{assert_de_tokens, assert_de_tokens_error, assert_tokens, Token};
-use str_index::{StrIndex, StrRange};
+use serde::{de::DeserializeSeed, Deserialize, Deserializer, Serialize};
+use serde_test::{
+    assert_de_tokens, assert_de_tokens_error, assert_ser_tokens_error, assert_tokens, Compact,
+    Configure, Readable, Token,
+};
+use str_index::{BoundedStrIndex, PackedStrRange, StrIndex, StrRange};
 
 #[test]
 fn str_index() {
@@ -7,14 +11,19 @@ fn str_index() {
         &StrIndex::from(0),
         &[Token::NewtypeStruct { name: "StrIndex" }, Token::U32(0)],
     );
-    assert_de_tokens(&StrIndex::from(0), &[Token::U32(0)])
+    assert_de_tokens(&StrIndex::from(0), &[Token::U32(0)]);
+    assert_de_tokens(&StrIndex::from(0), &[Token::U64(0)]);
+    assert_de_tokens_error::<StrIndex>(
+        &[Token::U64(1 << 32)],
+        "invalid value: integer `4294967296`, expected u32",
+    );
 }
 
 #[test]
 fn str_range() {
     let range = StrRange::from(StrIndex::from(0)..StrIndex::from(10));
     assert_tokens(
-        &range,
+        &range.readable(),
         &[
             Token::Struct {
                 name: "StrRange",
@@ -30,7 +39,7 @@ fn str_range() {
         ],
     );
     assert_de_tokens(
-        &range,
+        &range.readable(),
         &[
             Token::Map { len: Some(2) },
             Token::Str("start"),
@@ -41,7 +50,7 @@ fn str_range() {
         ],
     );
     assert_de_tokens(
-        &range,
+        &range.readable(),
         &[
             Token::Seq { len: Some(2) },
             Token::U32(0),
@@ -50,7 +59,7 @@ fn str_range() {
         ],
     );
 
-    assert_de_tokens_error::<StrRange>(
+    assert_de_tokens_error::<Readable<StrRange>>(
         &[
             Token::Seq { len: Some(2) },
             Token::U32(10),
@@ -59,7 +68,7 @@ fn str_range() {
         ],
         "invalid string range 10..0",
     );
-    assert_de_tokens_error::<StrRange>(
+    assert_de_tokens_error::<Readable<StrRange>>(
         &[
             Token::Struct {
                 name: "StrRange",
@@ -73,4 +82,340 @@ fn str_range() {
         ],
         "invalid string range 10..0",
     );
+
+    // binary formats go through `deserialize_struct` rather than `deserialize_any`
+    assert_tokens(
+        &range.compact(),
+        &[
+            Token::Struct {
+                name: "StrRange",
+                len: 2,
+            },
+            Token::Str("start"),
+            Token::NewtypeStruct { name: "StrIndex" },
+            Token::U32(0),
+            Token::Str("end"),
+            Token::NewtypeStruct { name: "StrIndex" },
+            Token::U32(10),
+            Token::StructEnd,
+        ],
+    );
+    assert_de_tokens(
+        &range.compact(),
+        &[
+            Token::Map { len: Some(2) },
+            Token::Str("start"),
+            Token::U32(0),
+            Token::Str("end"),
+            Token::U32(10),
+            Token::MapEnd,
+        ],
+    );
+    assert_de_tokens(
+        &range.compact(),
+        &[
+            Token::Seq { len: Some(2) },
+            Token::U32(0),
+            Token::U32(10),
+            Token::SeqEnd,
+        ],
+    );
+
+    assert_de_tokens_error::<Compact<StrRange>>(
+        &[
+            Token::Seq { len: Some(2) },
+            Token::U32(10),
+            Token::U32(0),
+            Token::SeqEnd,
+        ],
+        "invalid string range 10..0",
+    );
+    assert_de_tokens_error::<Compact<StrRange>>(
+        &[
+            Token::Struct {
+                name: "StrRange",
+                len: 2,
+            },
+            Token::Str("start"),
+            Token::U32(10),
+            Token::Str("end"),
+            Token::U32(0),
+            Token::StructEnd,
+        ],
+        "invalid string range 10..0",
+    );
+}
+
+#[test]
+fn str_range_from_integer() {
+    let range = StrIndex::from(5).as_unit_range();
+    assert_de_tokens(&range.readable(), &[Token::U32(5)]);
+    assert_de_tokens(&range.readable(), &[Token::U64(5)]);
+    assert_eq!(serde_json::from_str::<StrRange>("5").unwrap(), range);
+    assert_de_tokens_error::<Readable<StrRange>>(
+        &[Token::U64(1 << 32)],
+        "invalid value: integer `4294967296`, expected struct StrRange or u32",
+    );
+}
+
+#[test]
+fn packed_str_range() {
+    let range = StrRange::from(StrIndex::from(1)..StrIndex::from(10));
+    assert_tokens(&PackedStrRange(range), &[Token::U64(1 << 32 | 10)]);
+
+    assert_de_tokens_error::<PackedStrRange>(
+        &[Token::U64(10 << 32 | 1)],
+        "invalid string range 10..1",
+    );
+}
+
+#[test]
+fn str_index_bounded() {
+    #[derive(Debug, PartialEq)]
+    struct Bounded(StrIndex);
+
+    impl<'de> Deserialize<'de> for Bounded {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            StrIndex::deserialize_bounded(deserializer, 10.into()).map(Bounded)
+        }
+    }
+
+    assert_de_tokens(&Bounded(0.into()), &[Token::U32(0)]);
+    assert_de_tokens(
+        &Bounded(10.into()),
+        &[Token::NewtypeStruct { name: "StrIndex" }, Token::U32(10)],
+    );
+    assert_de_tokens_error::<Bounded>(
+        &[Token::U32(11)],
+        "string index 11 out of bounds (maximum 10)",
+    );
+
+    let seed = BoundedStrIndex { max: 10.into() };
+    assert_eq!(
+        seed.deserialize(serde_json::Value::from(0)).unwrap(),
+        StrIndex::from(0),
+    );
+    assert_eq!(
+        seed.deserialize(serde_json::Value::from(10)).unwrap(),
+        StrIndex::from(10),
+    );
+    assert_eq!(
+        seed.deserialize(serde_json::Value::from(11))
+            .unwrap_err()
+            .to_string(),
+        "string index 11 out of bounds (maximum 10)",
+    );
+}
+
+#[test]
+fn str_range_inclusive() {
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Span {
+        #[serde(with = "str_index::serde_repr::inclusive")]
+        range: StrRange,
+    }
+
+    let span = Span {
+        range: StrRange::from(StrIndex::from(0)..StrIndex::from(10)),
+    };
+    assert_tokens(
+        &span,
+        &[
+            Token::Struct {
+                name: "Span",
+                len: 1,
+            },
+            Token::Str("range"),
+            Token::Struct {
+                name: "StrRangeInclusive",
+                len: 2,
+            },
+            Token::Str("start"),
+            Token::NewtypeStruct { name: "StrIndex" },
+            Token::U32(0),
+            Token::Str("end_inclusive"),
+            Token::NewtypeStruct { name: "StrIndex" },
+            Token::U32(9),
+            Token::StructEnd,
+            Token::StructEnd,
+        ],
+    );
+    assert_de_tokens(
+        &Span {
+            range: StrRange::from(StrIndex::from(5)..StrIndex::from(6)),
+        },
+        &[
+            Token::Struct {
+                name: "Span",
+                len: 1,
+            },
+            Token::Str("range"),
+            Token::Seq { len: Some(2) },
+            Token::U32(5),
+            Token::U32(5),
+            Token::SeqEnd,
+            Token::StructEnd,
+        ],
+    );
+    assert_de_tokens(
+        &Span {
+            range: StrRange::from(StrIndex::from(5)..StrIndex::from(6)),
+        },
+        &[
+            Token::Struct {
+                name: "Span",
+                len: 1,
+            },
+            Token::Str("range"),
+            Token::Struct {
+                name: "StrRangeInclusive",
+                len: 2,
+            },
+            Token::Bytes(b"start"),
+            Token::U32(5),
+            Token::Bytes(b"end_inclusive"),
+            Token::U32(5),
+            Token::StructEnd,
+            Token::StructEnd,
+        ],
+    );
+    assert_de_tokens_error::<Span>(
+        &[
+            Token::Struct {
+                name: "Span",
+                len: 1,
+            },
+            Token::Str("range"),
+            Token::Struct {
+                name: "StrRangeInclusive",
+                len: 2,
+            },
+            Token::Bytes(b"end"),
+        ],
+        "unknown field `end`, expected `start` or `end_inclusive`",
+    );
+
+    assert_ser_tokens_error(
+        &Span {
+            range: StrIndex::from(5).as_unit_range(),
+        },
+        &[
+            Token::Struct {
+                name: "Span",
+                len: 1,
+            },
+            Token::Str("range"),
+        ],
+        "empty string range 5..5 cannot be represented inclusively",
+    );
+    assert_de_tokens_error::<Span>(
+        &[
+            Token::Struct {
+                name: "Span",
+                len: 1,
+            },
+            Token::Str("range"),
+            Token::Seq { len: Some(2) },
+            Token::U32(6),
+            Token::U32(5),
+            Token::SeqEnd,
+        ],
+        "invalid string range 6..=5",
+    );
+    assert_de_tokens_error::<Span>(
+        &[
+            Token::Struct {
+                name: "Span",
+                len: 1,
+            },
+            Token::Str("range"),
+            Token::Seq { len: Some(2) },
+            Token::U32(0),
+            Token::U32(u32::MAX),
+            Token::SeqEnd,
+        ],
+        "string range 0..=4294967295 too large",
+    );
+}
+
+#[test]
+fn str_range_as_array() {
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Span {
+        #[serde(with = "str_index::serde_repr::as_array")]
+        range: StrRange,
+    }
+
+    let span = Span {
+        range: StrRange::from(StrIndex::from(0)..StrIndex::from(10)),
+    };
+    assert_tokens(
+        &span,
+        &[
+            Token::Struct {
+                name: "Span",
+                len: 1,
+            },
+            Token::Str("range"),
+            Token::Tuple { len: 2 },
+            Token::NewtypeStruct { name: "StrIndex" },
+            Token::U32(0),
+            Token::NewtypeStruct { name: "StrIndex" },
+            Token::U32(10),
+            Token::TupleEnd,
+            Token::StructEnd,
+        ],
+    );
+    assert_de_tokens(
+        &span,
+        &[
+            Token::Struct {
+                name: "Span",
+                len: 1,
+            },
+            Token::Str("range"),
+            Token::Seq { len: Some(2) },
+            Token::U32(0),
+            Token::U32(10),
+            Token::SeqEnd,
+            Token::StructEnd,
+        ],
+    );
+
+    assert_de_tokens_error::<Span>(
+        &[
+            Token::Struct {
+                name: "Span",
+                len: 1,
+            },
+            Token::Str("range"),
+            Token::Tuple { len: 2 },
+            Token::U32(10),
+            Token::U32(0),
+            Token::TupleEnd,
+        ],
+        "invalid string range 10..0",
+    );
+}
+
+#[test]
+fn str_range_flatten() {
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Spanned {
+        kind: String,
+        #[serde(flatten)]
+        range: StrRange,
+    }
+
+    let spanned = Spanned {
+        kind: "ident".into(),
+        range: StrRange::from(StrIndex::from(5)..StrIndex::from(10)),
+    };
+    let json = r#"{"kind":"ident","start":5,"end":10}"#;
+    assert_eq!(serde_json::to_string(&spanned).unwrap(), json);
+    assert_eq!(serde_json::from_str::<Spanned>(json).unwrap(), spanned);
+
+    let err =
+        serde_json::from_str::<Spanned>(r#"{"kind":"ident","start":10,"end":5}"#).unwrap_err();
+    assert!(err.to_string().contains("invalid string range 10..5"));
 }